@@ -0,0 +1,343 @@
+// BDF (Glyph Bitmap Distribution Format) のビットマップフォントを読み込む
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::font::{Font, Glyph};
+
+// 読み込みの失敗理由
+#[derive(Debug)]
+pub enum BdfError {
+    Io(std::io::Error),
+    // 行番号（1 始まり）と理由
+    Parse(usize, String),
+}
+
+impl std::fmt::Display for BdfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BdfError::Io(e) => write!(f, "{}", e),
+            BdfError::Parse(line, message) => write!(f, "{} 行目: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for BdfError {}
+
+// 1 文字のビットマップの幅の上限
+const MAX_GLYPH_WIDTH: usize = 32;
+
+// 1 文字のビットマップの高さの上限
+const MAX_GLYPH_HEIGHT: usize = 256;
+
+// ずれ・送り幅・FONT_ASCENT / FONT_DESCENT の絶対値の上限
+// （上端の位置の計算や、描画時に pixel_size を掛けても i32 に収まるようにする）
+const MAX_METRIC: i32 = 1024;
+
+// ENCODING の番号の意味（CHARSET_REGISTRY で決まる）
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Charset {
+    // ISO10646 (Unicode)、ISO8859-1、指定なし
+    Unicode,
+    // JIS X 0208 の区点（例: 0x3021 = "亜"）
+    Jisx0208,
+    // JIS X 0201（ASCII と半角カナ）
+    Jisx0201,
+}
+
+impl Charset {
+    fn new(registry: &str) -> Result<Self, String> {
+        let registry = registry.to_ascii_uppercase();
+        if registry.starts_with("JISX0208") {
+            Ok(Charset::Jisx0208)
+        } else if registry.starts_with("JISX0201") {
+            Ok(Charset::Jisx0201)
+        } else if registry.is_empty() || registry == "ISO10646" || registry == "ISO8859" {
+            Ok(Charset::Unicode)
+        } else {
+            Err(format!("対応していない文字集合です: {}", registry))
+        }
+    }
+
+    // ENCODING の番号を文字に変換。対応する文字がなければ None
+    fn decode(self, code: u32) -> Option<char> {
+        match self {
+            Charset::Unicode => char::from_u32(code),
+            Charset::Jisx0208 => {
+                // 区点の各バイトに 0x80 を足すと EUC-JP になる
+                let bytes = [(code >> 8) as u8 | 0x80, code as u8 | 0x80];
+                let (text, had_errors) = encoding_rs::EUC_JP.decode_without_bom_handling(&bytes);
+                let mut chars = text.chars();
+                match (chars.next(), chars.next(), had_errors) {
+                    (Some(ch), None, false) => Some(ch),
+                    _ => None,
+                }
+            }
+            Charset::Jisx0201 => match code {
+                0x20..=0x7e => char::from_u32(code),
+                // 半角カナ
+                0xa1..=0xdf => char::from_u32(code - 0xa1 + 0xff61),
+                _ => None,
+            },
+        }
+    }
+}
+
+// 読み込み中の文字
+#[derive(Default)]
+struct CharState {
+    code: Option<u32>,
+    advance: Option<i32>,
+    // BBX: 幅、高さ、ベースライン基準の左下のずれ
+    bbx: Option<(usize, usize, i32, i32)>,
+    rows: Option<Vec<u32>>,
+}
+
+pub fn load(path: &Path) -> Result<Font, BdfError> {
+    let text = std::fs::read(path).map_err(BdfError::Io)?;
+    // 古いフォントはコメントなどが Latin-1 のことがある
+    parse(&String::from_utf8_lossy(&text))
+}
+
+pub fn parse(text: &str) -> Result<Font, BdfError> {
+    let mut font_bbx: Option<(usize, usize, i32, i32)> = None;
+    let mut ascent: Option<i32> = None;
+    let mut descent: Option<i32> = None;
+    let mut registry = String::new();
+    let mut glyphs = HashMap::new();
+    let mut current: Option<CharState> = None;
+    let mut started = false;
+
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+    while let Some((line_no, line)) = lines.next() {
+        let error = |message: &str| BdfError::Parse(line_no, message.to_string());
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        let numbers = |count: usize| -> Result<Vec<i32>, BdfError> {
+            let values: Vec<i32> = args
+                .iter()
+                .take(count)
+                .filter_map(|a| a.parse().ok())
+                .collect();
+            if values.len() == count {
+                Ok(values)
+            } else {
+                Err(error(&format!(
+                    "{} には数値が {} 個必要です",
+                    keyword, count
+                )))
+            }
+        };
+        let metric = |value: i32| -> Result<i32, BdfError> {
+            if (-MAX_METRIC..=MAX_METRIC).contains(&value) {
+                Ok(value)
+            } else {
+                Err(error(&format!(
+                    "{} の値は -{}〜{} で指定してください",
+                    keyword, MAX_METRIC, MAX_METRIC
+                )))
+            }
+        };
+        let bbx = |values: Vec<i32>| -> Result<(usize, usize, i32, i32), BdfError> {
+            match values[..] {
+                [w, h, x, y] if w >= 0 && h >= 0 => {
+                    if h as usize > MAX_GLYPH_HEIGHT {
+                        return Err(error(&format!(
+                            "文字の高さは {} までです",
+                            MAX_GLYPH_HEIGHT
+                        )));
+                    }
+                    Ok((metric(w)? as usize, h as usize, metric(x)?, metric(y)?))
+                }
+                _ => Err(error("幅と高さは 0 以上を指定してください")),
+            }
+        };
+
+        match keyword {
+            "STARTFONT" => started = true,
+            _ if !started => return Err(error("STARTFONT がありません（BDF ではありません）")),
+            "FONTBOUNDINGBOX" => font_bbx = Some(bbx(numbers(4)?)?),
+            "FONT_ASCENT" => ascent = Some(metric(numbers(1)?[0])?),
+            "FONT_DESCENT" => descent = Some(metric(numbers(1)?[0])?),
+            "CHARSET_REGISTRY" => registry = line[keyword.len()..].trim().trim_matches('"').into(),
+            "STARTCHAR" => current = Some(CharState::default()),
+            "ENCODING" => {
+                let state = current
+                    .as_mut()
+                    .ok_or_else(|| error("STARTCHAR の外です"))?;
+                // -1 は符号化されていない文字
+                state.code = u32::try_from(numbers(1)?[0]).ok();
+            }
+            "DWIDTH" => {
+                let state = current
+                    .as_mut()
+                    .ok_or_else(|| error("STARTCHAR の外です"))?;
+                state.advance = Some(metric(numbers(2)?[0])?);
+            }
+            "BBX" => {
+                let state = current
+                    .as_mut()
+                    .ok_or_else(|| error("STARTCHAR の外です"))?;
+                state.bbx = Some(bbx(numbers(4)?)?);
+            }
+            "BITMAP" => {
+                let state = current
+                    .as_mut()
+                    .ok_or_else(|| error("STARTCHAR の外です"))?;
+                let (width, height, _, _) = state
+                    .bbx
+                    .or(font_bbx)
+                    .ok_or_else(|| error("BBX がありません"))?;
+                if width > MAX_GLYPH_WIDTH {
+                    return Err(error(&format!("文字の幅は {} までです", MAX_GLYPH_WIDTH)));
+                }
+                let mut rows = Vec::with_capacity(height);
+                for _ in 0..height {
+                    let (line_no, hex) = lines
+                        .next()
+                        .ok_or_else(|| error("ビットマップの行が足りません"))?;
+                    // 各行は左詰めでバイト境界まで埋められている（余分なバイトは無視）
+                    let bytes = width.div_ceil(8);
+                    let value = hex
+                        .get(..bytes * 2)
+                        .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+                        .ok_or_else(|| {
+                            BdfError::Parse(line_no, format!("ビットマップの行が不正です: {}", hex))
+                        })?;
+                    rows.push((value >> (bytes * 8 - width)) as u32);
+                }
+                state.rows = Some(rows);
+            }
+            "ENDCHAR" => {
+                let state = current.take().ok_or_else(|| error("STARTCHAR の外です"))?;
+                let charset = Charset::new(&registry).map_err(|message| error(&message))?;
+                let Some(ch) = state.code.and_then(|code| charset.decode(code)) else {
+                    continue;
+                };
+                let (width, height, x_offset, y_offset) = state
+                    .bbx
+                    .or(font_bbx)
+                    .ok_or_else(|| error("BBX がありません"))?;
+                let rows = state.rows.unwrap_or_else(|| vec![0; height]);
+                let advance = state.advance.unwrap_or(width as i32);
+                glyphs.insert(ch, (width, rows, x_offset, y_offset, advance));
+            }
+            _ => {}
+        }
+    }
+
+    // 行の高さとベースラインは FONT_ASCENT / FONT_DESCENT、なければ FONTBOUNDINGBOX から
+    let (_, bbx_height, _, bbx_y) = font_bbx.unwrap_or_default();
+    let ascent = ascent.unwrap_or(bbx_height as i32 + bbx_y);
+    let descent = descent.unwrap_or(-bbx_y);
+    if glyphs.is_empty() {
+        return Err(BdfError::Parse(0, "文字がありません".to_string()));
+    }
+
+    let glyphs = glyphs
+        .into_iter()
+        .map(|(ch, (width, rows, x_offset, y_offset, advance))| {
+            // BDF の y はベースラインから左下までの上向きのずれ
+            let top = ascent - (y_offset + rows.len() as i32);
+            (ch, Glyph::new(width, rows, x_offset, top, advance))
+        })
+        .collect();
+    Ok(Font::new(ascent + descent, ascent, glyphs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+STARTFONT 2.1
+FONT -test-fixed-medium-r-normal--6-60-75-75-c-40-iso10646-1
+SIZE 6 75 75
+FONTBOUNDINGBOX 4 6 0 -1
+STARTPROPERTIES 2
+FONT_ASCENT 5
+FONT_DESCENT 1
+ENDPROPERTIES
+CHARS 2
+STARTCHAR A
+ENCODING 65
+SWIDTH 666 0
+DWIDTH 4 0
+BBX 3 5 0 0
+BITMAP
+40
+A0
+E0
+A0
+A0
+ENDCHAR
+STARTCHAR period
+ENCODING 46
+DWIDTH 2 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn parses_glyphs_with_their_metrics() {
+        let font = parse(SAMPLE).unwrap();
+        assert_eq!((font.height, font.ascent), (6, 5));
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!((a.width, a.height, a.y_offset, a.advance), (3, 5, 0, 4));
+        // "." はベースラインの直上の 1 行
+        let period = font.glyph('.').unwrap();
+        assert_eq!((period.width, period.height, period.y_offset), (1, 1, 4));
+
+        // 最後の文字は送り幅ではなくビットマップの右端まで
+        assert_eq!(font.text_width("A.A"), 4 + 2 + 3);
+    }
+
+    #[test]
+    fn jis_encoded_fonts_are_converted_to_unicode() {
+        let text = SAMPLE
+            .replace("CHARS 2", "CHARSET_REGISTRY \"JISX0208.1983\"\nCHARS 2")
+            .replace("ENCODING 65", "ENCODING 12321");
+        let font = parse(&text).unwrap();
+        // 0x3021 は "亜"
+        assert!(font.glyph('亜').is_some());
+        assert!(font.glyph('A').is_none());
+    }
+
+    #[test]
+    fn errors_report_the_line() {
+        let text = SAMPLE.replace("A0\nE0", "A0\nZZ");
+        match parse(&text) {
+            Err(BdfError::Parse(line, _)) => assert_eq!(line, 18),
+            other => panic!("{:?}", other.map(|font| font.height)),
+        }
+        assert!(matches!(parse("hello"), Err(BdfError::Parse(1, _))));
+    }
+
+    #[test]
+    fn oversized_metrics_are_rejected() {
+        // 巨大な確保や桁あふれで落ちずにエラーになる
+        for bbx in [
+            "BBX 3 2000000000 0 0",
+            "BBX 3 5 0 2147483647",
+            "BBX 3 -5 0 0",
+        ] {
+            let text = SAMPLE.replace("BBX 3 5 0 0", bbx);
+            assert!(
+                matches!(parse(&text), Err(BdfError::Parse(14, _))),
+                "{}",
+                bbx
+            );
+        }
+        let text = SAMPLE.replace("FONT_ASCENT 5", "FONT_ASCENT -2147483648");
+        assert!(matches!(parse(&text), Err(BdfError::Parse(6, _))));
+    }
+}
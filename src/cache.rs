@@ -0,0 +1,124 @@
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::weather::Weather;
+
+// 最後に取得できた天気データと取得時刻
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CachedWeather {
+    // 取得時刻 (UNIX 秒)
+    pub fetched_at: i64,
+    pub weather: Weather,
+}
+
+impl CachedWeather {
+    pub fn fetched_at(&self) -> Option<DateTime<Local>> {
+        Local.timestamp_opt(self.fetched_at, 0).single()
+    }
+}
+
+// 保存先: $XDG_STATE_HOME/home_clock/weather.json（未設定なら ~/.local/state 以下）
+pub fn cache_path() -> Option<PathBuf> {
+    let state_dir = match env::var_os("XDG_STATE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".local/state"),
+    };
+    Some(state_dir.join("home_clock").join("weather.json"))
+}
+
+// 一時ファイルに書いてから置き換える（書き込み途中で電源が落ちても壊れないように）
+pub fn save(path: &Path, cached: &CachedWeather) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(cached)?)?;
+    std::fs::rename(&tmp, path)
+}
+
+pub fn load(path: &Path) -> io::Result<CachedWeather> {
+    let data = std::fs::read(path)?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+// 取得した天気データを保存（失敗しても表示には影響しないのでログのみ）
+pub fn store(weather: Weather, fetched_at: &DateTime<Local>) -> Weather {
+    let Some(path) = cache_path() else {
+        return weather;
+    };
+    let cached = CachedWeather {
+        fetched_at: fetched_at.timestamp(),
+        weather,
+    };
+    if let Err(e) = save(&path, &cached) {
+        eprintln!("天気データを {} に保存できません: {}", path.display(), e);
+    }
+    cached.weather
+}
+
+// 保存済みの天気データを読み込む（なければ None）
+pub fn restore() -> Option<CachedWeather> {
+    let path = cache_path()?;
+    match load(&path) {
+        Ok(cached) => Some(cached),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => {
+            eprintln!(
+                "保存済みの天気データを読み込めません: {}: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::{Current, Hour, WeatherIconType};
+    use chrono::NaiveDate;
+
+    fn hour(h: u32, time_epoch: i64) -> Hour {
+        Hour {
+            time: NaiveDate::from_ymd_opt(2026, 1, 12)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap(),
+            time_epoch,
+            temp_c: Some(5.0),
+            chance_of_rain: 0,
+            icon: WeatherIconType::Sun,
+        }
+    }
+
+    #[test]
+    fn restored_weather_is_filtered_against_current_time() {
+        let dir = env::temp_dir().join(format!("home_clock_cache_{}", std::process::id()));
+        let path = dir.join("weather.json");
+        let cached = CachedWeather {
+            fetched_at: 1000,
+            weather: Weather {
+                current: Current {
+                    temp_c: 5.0,
+                    icon: WeatherIconType::Sun,
+                },
+                hours: vec![hour(9, 1000), hour(10, 4600), hour(11, 8200)],
+                days: Vec::new(),
+            },
+        };
+        save(&path, &cached).unwrap();
+
+        let restored = load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(restored.fetched_at, 1000);
+
+        // 取得時刻ではなく表示する時刻より後の予報だけを使う
+        let upcoming = restored.weather.upcoming(5000, 4);
+        let hours: Vec<i64> = upcoming.iter().map(|h| h.time_epoch).collect();
+        assert_eq!(hours, vec![8200]);
+    }
+}
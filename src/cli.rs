@@ -0,0 +1,233 @@
+use chrono::NaiveDateTime;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::config::MAX_DISPLAY_SIZE;
+#[cfg(target_os = "linux")]
+use crate::fbdev::FbFormat;
+
+// コマンドライン引数。サブコマンドを省略すると run と同じ
+#[derive(Debug, Parser)]
+#[command(name = "home_clock", about = "ピクセルアート風の時計と天気予報")]
+pub struct Cli {
+    /// 設定ファイル（省略時は $XDG_CONFIG_HOME/home_clock/config.toml）
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    // サブコマンドなしで起動したときの run の指定（何も指定しなければ None）
+    #[command(flatten)]
+    pub run: Option<RunArgs>,
+}
+
+impl Cli {
+    // 引数を解析する。サブコマンドの前に書いた run の指定は黙って捨てずにエラーにする
+    pub fn parse_args() -> Self {
+        Self::parse().check().unwrap_or_else(|e| e.exit())
+    }
+
+    fn check(self) -> Result<Self, clap::Error> {
+        if self.command.is_some() && self.run.is_some() {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "run の指定はサブコマンドの後に書いてください（例: home_clock run --size 1024x600）",
+            ));
+        }
+        Ok(self)
+    }
+
+    // 実行するサブコマンド（省略時は run）
+    pub fn into_command(self) -> Command {
+        self.command
+            .unwrap_or_else(|| Command::Run(self.run.unwrap_or_default()))
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 時計を表示する（ウィンドウまたはフレームバッファデバイス）
+    Run(RunArgs),
+    /// 指定時刻の画面を PNG に書き出す
+    Render(RenderArgs),
+    /// 天気を取得して結果を表示する
+    Fetch(FetchArgs),
+    /// 設定ファイルを検証する
+    CheckConfig,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    /// 画面サイズ（例: 1024x600、設定ファイルより優先）
+    #[arg(long, value_name = "WxH", value_parser = parse_size)]
+    pub size: Option<(usize, usize)>,
+
+    /// 表示を始める時刻（例: "2026-01-12 23:30"、デモ・確認用）
+    #[arg(long, value_name = "DATETIME", value_parser = parse_datetime)]
+    pub at: Option<NaiveDateTime>,
+
+    /// 時計を進める速さの倍率（例: 60 で1秒ごとに1分進む）
+    #[arg(long, value_name = "FACTOR", value_parser = parse_speed)]
+    pub speed: Option<f64>,
+
+    /// ウィンドウの代わりに出力するフレームバッファデバイス（例: /dev/fb0）
+    #[cfg(target_os = "linux")]
+    #[arg(long, value_name = "PATH")]
+    pub fbdev: Option<PathBuf>,
+
+    /// 通常ファイルなど ioctl が使えない出力先の画面形式（例: 800x480x16）
+    #[cfg(target_os = "linux")]
+    #[arg(long, value_name = "WxHxBPP", requires = "fbdev", value_parser = parse_fb_format)]
+    pub fbdev_format: Option<FbFormat>,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// 描画する時刻（例: "2026-01-12 09:30"、省略時は現在時刻）
+    #[arg(long, value_name = "DATETIME", value_parser = parse_datetime)]
+    pub at: Option<NaiveDateTime>,
+
+    /// 書き出し先（1枚ならファイル、連番なら出力ディレクトリ）
+    #[arg(long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// 画面サイズ（例: 1024x600、設定ファイルより優先）
+    #[arg(long, value_name = "WxH", value_parser = parse_size)]
+    pub size: Option<(usize, usize)>,
+
+    /// 天気を取得せずに使う JSON（fetch --dump の出力形式）
+    #[arg(long, value_name = "PATH")]
+    pub weather: Option<PathBuf>,
+
+    /// 書き出す枚数
+    #[arg(long, default_value_t = 1, value_parser = parse_count)]
+    pub frames: usize,
+
+    /// 連番で書き出すときの時刻の間隔（秒）
+    #[arg(long, value_name = "SECS", default_value_t = 60)]
+    pub interval: i64,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// 取得した天気を JSON で出力する
+    #[arg(long)]
+    pub dump: bool,
+}
+
+// 画面の幅・高さがどちらも 1〜MAX_DISPLAY_SIZE
+fn valid_size(width: usize, height: usize) -> bool {
+    let range = 1..=MAX_DISPLAY_SIZE;
+    range.contains(&width) && range.contains(&height)
+}
+
+// "1024x600" 形式の画面サイズ（上限は MAX_DISPLAY_SIZE）
+fn parse_size(s: &str) -> Result<(usize, usize), String> {
+    let invalid = || {
+        format!(
+            "形式が不正です: {} (例: 1024x600、幅と高さは 1〜{})",
+            s, MAX_DISPLAY_SIZE
+        )
+    };
+    let (width, height) = s.split_once('x').ok_or_else(invalid)?;
+    match (width.parse(), height.parse()) {
+        (Ok(width), Ok(height)) if valid_size(width, height) => Ok((width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_datetime(s: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M"))
+        .map_err(|_| format!("形式が不正です: {} (例: 2026-01-12 09:30)", s))
+}
+
+fn parse_count(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("1 以上の整数で指定してください: {}", s)),
+    }
+}
+
+fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(format!("正の数で指定してください: {}", s)),
+    }
+}
+
+// "800x480x16" 形式の画面形式
+#[cfg(target_os = "linux")]
+fn parse_fb_format(s: &str) -> Result<FbFormat, String> {
+    let invalid = || format!("形式が不正です: {} (例: 800x480x16)", s);
+    let parts: Vec<usize> = s
+        .split('x')
+        .map(|p| p.parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    match parts[..] {
+        [width, height, _] if !valid_size(width, height) => Err(invalid()),
+        [width, height, 16] => Ok(FbFormat::rgb565(width, height)),
+        [width, height, 32] => Ok(FbFormat::xrgb8888(width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn run_is_the_default_command() {
+        let cli = Cli::try_parse_from(["home_clock", "--size", "1024x600"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.run.unwrap().size, Some((1024, 600)));
+
+        let cli = Cli::try_parse_from([
+            "home_clock",
+            "render",
+            "--at",
+            "2026-01-12 09:30",
+            "--config",
+            "clock.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("clock.toml")));
+        match cli.command {
+            Some(Command::Render(args)) => {
+                assert_eq!(args.at.unwrap().to_string(), "2026-01-12 09:30:00");
+                assert_eq!((args.frames, args.interval), (1, 60));
+            }
+            other => panic!("{:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["home_clock", "--config", "clock.toml", "check-config"]);
+        assert!(matches!(cli.unwrap().command, Some(Command::CheckConfig)));
+
+        assert!(Cli::try_parse_from(["home_clock", "render", "--size", "big"]).is_err());
+        assert!(Cli::try_parse_from(["home_clock", "render", "--frames", "0"]).is_err());
+        assert!(Cli::try_parse_from(["home_clock", "render", "--size", "100000x100000"]).is_err());
+
+        // サブコマンドの前の run の指定は黙って捨てない
+        let cli = Cli::try_parse_from(["home_clock", "--size", "1024x600", "run"]).unwrap();
+        assert!(cli.check().is_err());
+        let cli = Cli::try_parse_from([
+            "home_clock",
+            "--config",
+            "clock.toml",
+            "run",
+            "--size",
+            "1024x600",
+        ]);
+        match cli.unwrap().check().unwrap().into_command() {
+            Command::Run(args) => assert_eq!(args.size, Some((1024, 600))),
+            other => panic!("{:?}", other),
+        }
+    }
+}
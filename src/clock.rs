@@ -1,109 +1,233 @@
-use chrono::Weekday;
+use chrono::{Datelike, NaiveDate};
 use embedded_graphics::pixelcolor::Rgb888;
+use serde::Deserialize;
 
-use crate::font::{draw_colon, draw_digit, draw_hyphen, draw_text, DIGIT_WIDTH};
+use crate::font::Font;
 use crate::framebuffer::FrameBuffer;
+use crate::layout::{fit_pixel_size, Rect};
+use crate::locale::Locale;
+use crate::text::TextStyle;
+
+// 時の表記
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum HourFormat {
+    #[default]
+    #[serde(rename = "24h")]
+    TwentyFour,
+    // 1〜12 時に午前・午後の表示を添える
+    #[serde(rename = "12h")]
+    Twelve,
+}
+
+// 午前・午後の表記
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Meridiem {
+    // "9:05 PM" のように後ろに付ける
+    #[default]
+    En,
+    // "午後9:05" のように前に付ける
+    Ja,
+}
+
+// 時刻の表示形式（設定ファイルの [clock]）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeFormat {
+    pub hour_format: HourFormat,
+    pub meridiem: Meridiem,
+}
+
+impl TimeFormat {
+    // 表示する時と午前・午後の表記（24時間表記では None）
+    fn hour_and_marker(&self, hour: u8) -> (u8, Option<&'static str>) {
+        if self.hour_format == HourFormat::TwentyFour {
+            return (hour, None);
+        }
+        let marker = match (self.meridiem, hour < 12) {
+            (Meridiem::En, true) => "AM",
+            (Meridiem::En, false) => "PM",
+            (Meridiem::Ja, true) => "午前",
+            (Meridiem::Ja, false) => "午後",
+        };
+        // 正午・深夜は 12 時（午前12時〜午後11時）
+        let hour = match hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        (hour, Some(marker))
+    }
 
-fn get_weekday_str(weekday: Weekday) -> &'static str {
-    match weekday {
-        Weekday::Mon => "MON",
-        Weekday::Tue => "TUE",
-        Weekday::Wed => "WED",
-        Weekday::Thu => "THU",
-        Weekday::Fri => "FRI",
-        Weekday::Sat => "SAT",
-        Weekday::Sun => "SUN",
+    // 予報の時刻の表記 ("09:00", "9AM", "午前9時")
+    pub fn hour_label(&self, hour: u8) -> String {
+        match self.hour_and_marker(hour) {
+            (hour, None) => format!("{:02}:00", hour),
+            (hour, Some(marker)) if self.meridiem == Meridiem::Ja => {
+                format!("{}{}時", marker, hour)
+            }
+            (hour, Some(marker)) => format!("{}{}", hour, marker),
+        }
     }
 }
 
-pub fn draw_date(fb: &mut FrameBuffer, year: u16, month: u8, day: u8, weekday: Weekday) {
-    let pixel_size = 3;
-    let digit_width = DIGIT_WIDTH as i32 * pixel_size;
-    let hyphen_width = DIGIT_WIDTH as i32 * pixel_size;
-    let letter_width = DIGIT_WIDTH as i32 * pixel_size;
-    let spacing = pixel_size;
-
-    // YYYY-MM-DD WEEKDAYの全体幅を計算
-    let weekday_str = get_weekday_str(weekday);
-    let weekday_width = (weekday_str.len() as i32) * (letter_width + spacing) - spacing;
-    let date_width = digit_width * 8 + hyphen_width * 2 + spacing * 9;
-    let total_width = date_width + spacing * 2 + weekday_width;
-
-    let start_x = (800 - total_width) / 2;
-    let start_y = 40;
-
-    let color = Rgb888::new(180, 180, 180);
-
-    let mut x = start_x;
-
-    // 年 (4桁)
-    draw_digit(
-        fb,
-        ((year / 1000) % 10) as u8,
-        x,
-        start_y,
-        pixel_size,
-        color,
-    );
-    x += digit_width + spacing;
-    draw_digit(fb, ((year / 100) % 10) as u8, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, ((year / 10) % 10) as u8, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, (year % 10) as u8, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-
-    // ハイフン
-    draw_hyphen(fb, x, start_y, pixel_size, color);
-    x += hyphen_width + spacing;
-
-    // 月
-    draw_digit(fb, month / 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, month % 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-
-    // ハイフン
-    draw_hyphen(fb, x, start_y, pixel_size, color);
-    x += hyphen_width + spacing;
-
-    // 日
-    draw_digit(fb, day / 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, day % 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing * 3;
-
-    // 曜日
-    draw_text(fb, weekday_str, x, start_y, pixel_size, color);
+// 英語表記の日付と曜日の間隔（pixel_size=1 のとき）
+const WEEKDAY_GAP: i32 = 3;
+
+// 日付の表記と、英語表記で後ろに離して置く曜日（"2026-01-12" + "MON" / "1月12日(月)"）
+fn date_text(date: NaiveDate, locale: Locale) -> (String, Option<&'static str>) {
+    let weekday = locale.weekday(date.weekday());
+    match locale {
+        Locale::En => (date.format("%Y-%m-%d").to_string(), Some(weekday)),
+        Locale::Ja => (
+            format!("{}月{}日({})", date.month(), date.day(), weekday),
+            None,
+        ),
+    }
 }
 
-pub fn draw_time(fb: &mut FrameBuffer, hour: u8, minute: u8, blink: bool) {
-    let pixel_size = 16;
-    let digit_width = DIGIT_WIDTH as i32 * pixel_size;
-    let colon_width = 2 * pixel_size;
-    let spacing = pixel_size;
+// 日付表示の大きさ（pixel_size=1 のとき）
+pub fn date_unit_size(date: NaiveDate, font: &Font, locale: Locale) -> (i32, i32) {
+    let (text, suffix) = date_text(date, locale);
+    let width = font.text_width(&text) + suffix.map_or(0, |s| WEEKDAY_GAP + font.text_width(s));
+    (width, font.height)
+}
 
-    // HH:MM の全体幅を計算
-    let total_width = digit_width * 4 + colon_width + spacing * 4;
-    let start_x = (800 - total_width) / 2;
-    let start_y = 120;
+// 日付を region 内に中央寄せで表示
+pub fn draw_date(
+    fb: &mut FrameBuffer,
+    region: &Rect,
+    date: NaiveDate,
+    font: &Font,
+    locale: Locale,
+    color: Rgb888,
+) {
+    let (text, suffix) = date_text(date, locale);
+    let (unit_width, unit_height) = date_unit_size(date, font, locale);
+    let style = TextStyle::new(font, fit_pixel_size(unit_width, unit_height, region));
+
+    let x = region.centered_x(unit_width * style.pixel_size);
+    style.draw_at(fb, &text, x, region.y, color);
+    if let Some(suffix) = suffix {
+        let suffix_x = x + style.width(&text) + WEEKDAY_GAP * style.pixel_size;
+        style.draw_at(fb, suffix, suffix_x, region.y, color);
+    }
+}
 
-    let color = Rgb888::new(255, 255, 255);
+// 大きな時刻表示に対する午前・午後の表記の大きさの比
+const MARKER_RATIO: i32 = 3;
 
-    let mut x = start_x;
+// 時刻表示の各部分の文字列
+struct TimeText {
+    hour: String,
+    minute: String,
+    marker: Option<&'static str>,
+    // 午前・午後の表記と時刻の間隔（pixel_size=1 のとき）
+    marker_gap: i32,
+}
 
-    // 時
-    draw_digit(fb, hour / 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, hour % 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
+impl TimeText {
+    fn new(hour: u8, minute: u8, format: TimeFormat) -> Self {
+        let (hour, marker) = format.hour_and_marker(hour);
+        // 12時間表記では 1〜9 時を1桁で表示
+        let hour = match marker {
+            Some(_) => hour.to_string(),
+            None => format!("{:02}", hour),
+        };
+        Self {
+            hour,
+            minute: format!("{:02}", minute),
+            marker,
+            marker_gap: if marker.is_some() { 2 } else { 0 },
+        }
+    }
 
-    // コロン
-    draw_colon(fb, x, start_y, pixel_size, color, blink);
-    x += colon_width + spacing;
+    fn digits(&self) -> String {
+        format!("{}:{}", self.hour, self.minute)
+    }
 
-    // 分
-    draw_digit(fb, minute / 10, x, start_y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, minute % 10, x, start_y, pixel_size, color);
+    // 全体の幅（pixel_size=1 のとき）。午前・午後の表記は時刻の 1/MARKER_RATIO の大きさ
+    fn unit_width(&self, font: &Font) -> i32 {
+        let marker_unit_width = self.marker.map_or(0, |m| font.text_width(m));
+        font.text_width(&self.digits())
+            + self.marker_gap
+            + (marker_unit_width + MARKER_RATIO - 1) / MARKER_RATIO
+    }
+}
+
+// 時刻表示の大きさ（pixel_size=1 のとき）
+pub fn time_unit_size(hour: u8, minute: u8, format: TimeFormat) -> (i32, i32) {
+    // 時刻は設定のフォントによらず内蔵フォントで描画する
+    let font = Font::builtin();
+    (
+        TimeText::new(hour, minute, format).unit_width(font),
+        font.height,
+    )
+}
+
+pub fn draw_time(
+    fb: &mut FrameBuffer,
+    region: &Rect,
+    hour: u8,
+    minute: u8,
+    blink: bool,
+    format: TimeFormat,
+    color: Rgb888,
+) {
+    let text = TimeText::new(hour, minute, format);
+    let font = Font::builtin();
+    let pixel_size = fit_pixel_size(text.unit_width(font), font.height, region);
+    let style = TextStyle::new(font, pixel_size);
+    let marker_style = TextStyle::new(font, (pixel_size / MARKER_RATIO).max(1));
+    let marker_width = text
+        .marker
+        .map_or(0, |m| marker_style.width(m) + text.marker_gap * pixel_size);
+    let mut x = region.centered_x(style.width(&text.digits()) + marker_width);
+    let y = region.y;
+
+    // 午前・午後は数字の下端に揃え、日本語では前、英語では後ろに置く
+    let marker_y = y + style.height() - marker_style.height();
+    if let (Some(marker), Meridiem::Ja) = (text.marker, format.meridiem) {
+        marker_style.draw_at(fb, marker, x, marker_y, color);
+        x += marker_width;
+    }
+
+    // 時・コロン・分（コロンは点滅させる）
+    style.draw_at(fb, &text.hour, x, y, color);
+    let colon_x = x + style.advance(&text.hour);
+    if blink {
+        style.draw_at(fb, ":", colon_x, y, color);
+    }
+    let minute_x = colon_x + style.advance(":");
+    style.draw_at(fb, &text.minute, minute_x, y, color);
+
+    if let (Some(marker), Meridiem::En) = (text.marker, format.meridiem) {
+        let marker_x = minute_x + style.width(&text.minute) + text.marker_gap * pixel_size;
+        marker_style.draw_at(fb, marker, marker_x, marker_y, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twelve_hour_labels() {
+        let en = TimeFormat {
+            hour_format: HourFormat::Twelve,
+            meridiem: Meridiem::En,
+        };
+        let ja = TimeFormat {
+            hour_format: HourFormat::Twelve,
+            meridiem: Meridiem::Ja,
+        };
+        let labels = |format: TimeFormat| -> Vec<String> {
+            [0, 9, 12, 23].map(|h| format.hour_label(h)).to_vec()
+        };
+        assert_eq!(
+            labels(TimeFormat::default()),
+            ["00:00", "09:00", "12:00", "23:00"]
+        );
+        assert_eq!(labels(en), ["12AM", "9AM", "12PM", "11PM"]);
+        assert_eq!(labels(ja), ["午前12時", "午前9時", "午後12時", "午後11時"]);
+    }
 }
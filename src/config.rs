@@ -0,0 +1,563 @@
+use embedded_graphics::pixelcolor::Rgb888;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::bdf::{self, BdfError};
+use crate::clock::TimeFormat;
+use crate::font::Font;
+use crate::locale::Locale;
+use crate::refresh::RefreshConfig;
+use crate::render::{Colors, Sections, Theme};
+use crate::widget::{Panel, Screen};
+
+// 設定ファイルの読み込み・検証の失敗理由
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, std::io::Error),
+    // TOML の文法や型の誤り（行・列を含む）
+    Parse(PathBuf, toml::de::Error),
+    // 値の範囲などの誤り
+    Invalid(&'static str, String),
+    // font に指定したフォントを読み込めない
+    Font(PathBuf, BdfError),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => {
+                write!(f, "設定ファイル {} を読み込めません: {}", path.display(), e)
+            }
+            ConfigError::Parse(path, e) => {
+                write!(f, "設定ファイル {} の形式が不正です: {}", path.display(), e)
+            }
+            ConfigError::Invalid(key, message) => write!(f, "設定 {} が不正です: {}", key, message),
+            ConfigError::Font(path, e) => {
+                write!(f, "フォント {} を読み込めません: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+impl ConfigError {
+    // 画面の状態表示用の短い表記
+    pub fn short_label(&self) -> &'static str {
+        "CONFIG ERROR"
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// "#rrggbb" 形式の色（# は省略可）
+pub fn parse_color(s: &str) -> Option<Rgb888> {
+    let s = s.trim();
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(Rgb888::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+pub fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rgb888, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_color(&s).ok_or_else(|| {
+        serde::de::Error::custom(format!("色は #rrggbb 形式で指定してください: {}", s))
+    })
+}
+
+// 画面の幅・高さの上限（--size なども含む）
+pub const MAX_DISPLAY_SIZE: usize = 8192;
+
+// [display] 画面
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    pub width: usize,
+    pub height: usize,
+    // ウィンドウ表示の最大フレームレート
+    pub fps: usize,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 480,
+            fps: 30,
+        }
+    }
+}
+
+// [refresh] 天気の再取得（分単位）
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RefreshSection {
+    pub interval_minutes: u64,
+    pub stale_after_minutes: u64,
+}
+
+impl Default for RefreshSection {
+    fn default() -> Self {
+        let defaults = RefreshConfig::default();
+        Self {
+            interval_minutes: defaults.interval.as_secs() / 60,
+            stale_after_minutes: defaults.stale_after.as_secs() / 60,
+        }
+    }
+}
+
+// [weather] 天気の取得元。provider で種類を選ぶ
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "provider", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum WeatherConfig {
+    // API キーは環境変数 WEATHERAPI_KEY でも指定できる（そちらが優先）
+    // location を省略すると環境変数 WEATHER_LOCATION（設定ファイル導入前の .env との互換）
+    Weatherapi {
+        location: Option<String>,
+        api_key: Option<String>,
+    },
+    OpenMeteo {
+        latitude: f64,
+        longitude: f64,
+    },
+    // 府県予報区 (例: "130000") と一次細分区域 (例: "130010"、省略時は最初の区域)
+    Jma {
+        office: String,
+        area: Option<String>,
+    },
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        WeatherConfig::Weatherapi {
+            location: None,
+            api_key: None,
+        }
+    }
+}
+
+// 設定ファイル全体。書かれていない項目は既定値
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub locale: Locale,
+    // BDF フォント（設定ファイルからの相対パス可、省略時は内蔵フォント）
+    pub font: Option<PathBuf>,
+    pub display: DisplayConfig,
+    pub colors: Colors,
+    pub layout: Sections,
+    // パネルの並べ方（省略時は日付・概要・時刻・現在の天気・予報を縦に並べる）
+    pub screen: Panel,
+    pub clock: TimeFormat,
+    pub refresh: RefreshSection,
+    pub weather: WeatherConfig,
+}
+
+impl Config {
+    // 既定の設定ファイル: $XDG_CONFIG_HOME/home_clock/config.toml（未設定なら ~/.config 以下）
+    pub fn default_path() -> Option<PathBuf> {
+        let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_dir.join("home_clock").join("config.toml"))
+    }
+
+    // 読み込むファイル。指定がなく既定の場所にもなければ None
+    pub fn source_path(path: Option<&Path>) -> Option<PathBuf> {
+        match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Self::default_path().filter(|path| path.exists()),
+        }
+    }
+
+    // path を指定しなければ既定の場所から読み、なければ既定値
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        Self::load_with_env_file(path, env_file_path().as_deref())
+    }
+
+    fn load_with_env_file(
+        path: Option<&Path>,
+        env_file: Option<&Path>,
+    ) -> Result<Self, ConfigError> {
+        let mut config = match Self::source_path(path) {
+            Some(path) => Self::read(&path)?,
+            None => Self::default(),
+        };
+
+        // 秘密情報は設定ファイルに書かずに環境変数や .env からも渡せる（環境変数が優先）
+        // .env は読み込むたびに読み直すので、実行中に書き換えても反映される
+        let env_file = env_file.map(read_env_file).unwrap_or_default();
+        config.apply_env(|key| env::var(key).ok().or_else(|| env_file.get(key).cloned()));
+        Ok(config)
+    }
+
+    // 環境変数の値を反映する（var は変数名から値を引く）
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let WeatherConfig::Weatherapi { location, api_key } = &mut self.weather {
+            if let Some(key) = var("WEATHERAPI_KEY") {
+                *api_key = Some(key);
+            }
+            if location.is_none() {
+                *location = var("WEATHER_LOCATION");
+            }
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self, ConfigError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        let mut config = Self::parse(&text).map_err(|e| match e {
+            ConfigError::Parse(_, e) => ConfigError::Parse(path.to_path_buf(), e),
+            other => other,
+        })?;
+        if let (Some(font), Some(dir)) = (&mut config.font, path.parent()) {
+            *font = dir.join(&*font);
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let config: Config =
+            toml::from_str(text).map_err(|e| ConfigError::Parse(PathBuf::new(), e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |key, message: &str| Err(ConfigError::Invalid(key, message.to_string()));
+
+        let display_size = 1..=MAX_DISPLAY_SIZE;
+        if !display_size.contains(&self.display.width)
+            || !display_size.contains(&self.display.height)
+        {
+            return invalid(
+                "display.width/height",
+                &format!("1〜{} の範囲で指定してください", MAX_DISPLAY_SIZE),
+            );
+        }
+        if !(1..=120).contains(&self.display.fps) {
+            return invalid("display.fps", "1〜120 の範囲で指定してください");
+        }
+        if self.refresh.interval_minutes == 0 {
+            return invalid("refresh.interval_minutes", "1 以上を指定してください");
+        }
+        if self.refresh.stale_after_minutes < self.refresh.interval_minutes {
+            return invalid(
+                "refresh.stale_after_minutes",
+                "interval_minutes 以上を指定してください",
+            );
+        }
+
+        if let Err(message) = self.screen.validate() {
+            return Err(ConfigError::Invalid("screen", message));
+        }
+
+        match &self.weather {
+            WeatherConfig::Weatherapi { .. } => {}
+            WeatherConfig::OpenMeteo {
+                latitude,
+                longitude,
+            } => {
+                if !(-90.0..=90.0).contains(latitude) {
+                    return invalid("weather.latitude", "-90〜90 の範囲で指定してください");
+                }
+                if !(-180.0..=180.0).contains(longitude) {
+                    return invalid("weather.longitude", "-180〜180 の範囲で指定してください");
+                }
+            }
+            WeatherConfig::Jma { office, area } => {
+                let is_code = |s: &str| s.len() == 6 && s.bytes().all(|b| b.is_ascii_digit());
+                if !is_code(office) {
+                    return invalid("weather.office", "6桁の数字で指定してください (例: 130000)");
+                }
+                if area.as_deref().is_some_and(|a| !is_code(a)) {
+                    return invalid("weather.area", "6桁の数字で指定してください (例: 130010)");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 見た目の設定。フォントの指定があればここで読み込む
+    pub fn theme(&self) -> Result<Theme, ConfigError> {
+        let font = match &self.font {
+            Some(path) => bdf::load(path).map_err(|e| ConfigError::Font(path.clone(), e))?,
+            None => Font::default(),
+        };
+        Ok(Theme {
+            colors: self.colors.clone(),
+            time_format: self.clock,
+            locale: self.locale,
+            font,
+            screen: Screen::new(&self.screen, &self.layout),
+        })
+    }
+
+    pub fn refresh(&self) -> RefreshConfig {
+        RefreshConfig {
+            interval: Duration::from_secs(self.refresh.interval_minutes * 60),
+            stale_after: Duration::from_secs(self.refresh.stale_after_minutes * 60),
+        }
+    }
+}
+
+// .env の場所。カレントディレクトリから親へ順に探し、なければカレントディレクトリの .env
+pub fn env_file_path() -> Option<PathBuf> {
+    let dir = env::current_dir().ok()?;
+    let found = dir
+        .ancestors()
+        .map(|d| d.join(".env"))
+        .find(|p| p.is_file());
+    Some(found.unwrap_or_else(|| dir.join(".env")))
+}
+
+// .env の変数（プロセスの環境変数は書き換えない）。読めなければ空
+// dotenv の非推奨でない関数は環境変数に書き込むだけで、既にある値を上書きしないので読み直しに使えない
+#[allow(deprecated)]
+fn read_env_file(path: &Path) -> HashMap<String, String> {
+    dotenv::from_path_iter(path)
+        .map(|iter| iter.filter_map(Result::ok).collect())
+        .unwrap_or_default()
+}
+
+// 設定ファイルを確認する間隔
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// 設定ファイルと .env の更新時刻を定期的に確認し、どちらかが変わっていれば読み直す
+pub struct ConfigWatcher {
+    // --config の指定
+    explicit: Option<PathBuf>,
+    // 確認するファイル（既定の場所はまだ存在しなくてもよい）
+    path: Option<PathBuf>,
+    env_file: Option<PathBuf>,
+    // 設定ファイルと .env の更新時刻
+    modified: (Option<SystemTime>, Option<SystemTime>),
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    // 起動時に読み込んだ時点の更新時刻を基準にする
+    pub fn new(explicit: Option<&Path>, now: Instant) -> Self {
+        Self::with_env_file(explicit, env_file_path(), now)
+    }
+
+    fn with_env_file(explicit: Option<&Path>, env_file: Option<PathBuf>, now: Instant) -> Self {
+        let path = explicit
+            .map(Path::to_path_buf)
+            .or_else(Config::default_path);
+        let mut watcher = Self {
+            explicit: explicit.map(Path::to_path_buf),
+            path,
+            env_file,
+            modified: (None, None),
+            last_check: now,
+        };
+        watcher.modified = watcher.modified_times();
+        watcher
+    }
+
+    fn modified_times(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+        (
+            self.path.as_deref().and_then(modified_time),
+            self.env_file.as_deref().and_then(modified_time),
+        )
+    }
+
+    // 前回の確認から変更があれば、読み直した結果
+    pub fn poll(&mut self, now: Instant) -> Option<Result<Config, ConfigError>> {
+        if now.duration_since(self.last_check) < WATCH_INTERVAL {
+            return None;
+        }
+        self.last_check = now;
+
+        let modified = self.modified_times();
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(Config::load_with_env_file(
+            self.explicit.as_deref(),
+            self.env_file.as_deref(),
+        ))
+    }
+}
+
+// ファイルがなければ None（削除も変更として扱う）
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_is_valid() {
+        let config = Config::parse(include_str!("../doc/config.example.toml")).unwrap();
+        assert_eq!((config.display.width, config.display.height), (800, 480));
+        assert_eq!(config.colors.background, Rgb888::new(0x00, 0x10, 0x20));
+        assert!(matches!(config.weather, WeatherConfig::Weatherapi { .. }));
+        assert_eq!(config.refresh().interval, Duration::from_secs(600));
+        assert_eq!(config.screen, Panel::default());
+    }
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.display.fps, 30);
+        assert!(config.layout.forecast);
+    }
+
+    #[test]
+    fn weatherapi_settings_can_come_from_the_environment() {
+        let vars = |key: &str| match key {
+            "WEATHERAPI_KEY" => Some("secret".to_string()),
+            "WEATHER_LOCATION" => Some("Osaka".to_string()),
+            _ => None,
+        };
+        let weatherapi = |config: Config| match config.weather {
+            WeatherConfig::Weatherapi { location, api_key } => (location, api_key),
+            other => panic!("{:?}", other),
+        };
+
+        // 以前の .env の WEATHER_LOCATION も使える
+        let mut config = Config::parse("").unwrap();
+        config.apply_env(vars);
+        assert_eq!(
+            weatherapi(config),
+            (Some("Osaka".to_string()), Some("secret".to_string()))
+        );
+
+        // 場所は設定ファイルの指定が優先
+        let mut config =
+            Config::parse("[weather]\nprovider = \"weatherapi\"\nlocation = \"Tokyo\"\n").unwrap();
+        config.apply_env(vars);
+        assert_eq!(weatherapi(config).0.as_deref(), Some("Tokyo"));
+    }
+
+    #[test]
+    fn invalid_values_are_reported_with_their_key() {
+        let error = Config::parse("[colors]\ntime = \"white\"\n").unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("#rrggbb"), "{}", message);
+        assert!(message.contains("line 2"), "{}", message);
+
+        let error = Config::parse("[display]\nfps = 0\n").unwrap_err();
+        assert!(error.to_string().contains("display.fps"));
+        let error = Config::parse("[display]\nwidth = 100000\nheight = 100000\n").unwrap_err();
+        assert!(error.to_string().contains("display.width"));
+
+        let error =
+            Config::parse("[weather]\nprovider = \"jma\"\noffice = \"tokyo\"\n").unwrap_err();
+        assert!(error.to_string().contains("weather.office"));
+
+        // 綴りの誤りも見逃さない
+        let error = Config::parse("[layout]\nforcast = false\n").unwrap_err();
+        assert!(error.to_string().contains("forcast"));
+    }
+
+    #[test]
+    fn screen_panels_are_checked() {
+        let config = Config::parse(
+            "[screen]\ntype = \"row\"\nchildren = [{ type = \"time\", size = 2 }, { type = \"forecast\" }]\n",
+        )
+        .unwrap();
+        assert_eq!(config.screen.children.len(), 2);
+        assert_eq!(config.screen.children[0].size, Some(2));
+
+        let error = Config::parse("[screen]\ntype = \"grid\"\nchildren = [{ type = \"date\" }]\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("columns"), "{}", error);
+        let error = Config::parse(
+            "[screen]\ntype = \"grid\"\ncolumns = 3000000000\nchildren = [{ type = \"date\" }]\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("columns"), "{}", error);
+        let error = Config::parse("[screen]\ntype = \"date\"\nchildren = [{ type = \"time\" }]\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("children"), "{}", error);
+        let error = Config::parse(
+            "[screen]\ntype = \"grid\"\ncolumns = 2\nchildren = [{ type = \"date\", size = 3 }, { type = \"time\" }]\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("size"), "{}", error);
+        let error = Config::parse(
+            "[screen]\ntype = \"column\"\nchildren = [{ type = \"date\", size = 2000000000 }, { type = \"time\", size = 2000000000 }]\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("size"), "{}", error);
+    }
+
+    #[test]
+    fn watcher_reloads_changed_file() {
+        let path = env::temp_dir().join(format!("home_clock_config_{}.toml", std::process::id()));
+        let write = |text: &str, secs: u64| {
+            std::fs::write(&path, text).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+        write("[colors]\ntime = \"#ffffff\"\n", 1000);
+
+        let start = Instant::now();
+        let mut watcher = ConfigWatcher::new(Some(&path), start);
+        assert!(watcher.poll(start + WATCH_INTERVAL).is_none());
+
+        // 確認間隔が過ぎるまでは読み直さない
+        write("[colors]\ntime = \"#ffcc00\"\n", 2000);
+        assert!(watcher.poll(start + WATCH_INTERVAL).is_none());
+        let config = watcher.poll(start + WATCH_INTERVAL * 2).unwrap().unwrap();
+        assert_eq!(config.colors.time, Rgb888::new(0xff, 0xcc, 0x00));
+
+        write("[colors]\ntime = \"yellow\"\n", 3000);
+        assert!(matches!(
+            watcher.poll(start + WATCH_INTERVAL * 3),
+            Some(Err(ConfigError::Parse(..)))
+        ));
+        assert!(watcher.poll(start + WATCH_INTERVAL * 4).is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn watcher_reloads_changed_env_file() {
+        let dir = env::temp_dir().join(format!("home_clock_env_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        let env_path = dir.join(".env");
+        std::fs::write(&config_path, "[weather]\nprovider = \"weatherapi\"\n").unwrap();
+        let write_env = |text: &str, secs: u64| {
+            std::fs::write(&env_path, text).unwrap();
+            let file = std::fs::File::options()
+                .write(true)
+                .open(&env_path)
+                .unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+        write_env("WEATHER_LOCATION=Tokyo\n", 1000);
+
+        let start = Instant::now();
+        let mut watcher =
+            ConfigWatcher::with_env_file(Some(&config_path), Some(env_path.clone()), start);
+        assert!(watcher.poll(start + WATCH_INTERVAL).is_none());
+
+        // 以前に読んだ値が残らず、新しい値に置き換わる
+        write_env("WEATHER_LOCATION=Osaka\n", 2000);
+        let config = watcher.poll(start + WATCH_INTERVAL * 2).unwrap().unwrap();
+        // 環境変数に設定されていればそちらが優先される
+        if env::var_os("WEATHER_LOCATION").is_none() {
+            assert!(matches!(
+                config.weather,
+                WeatherConfig::Weatherapi { location: Some(location), .. } if location == "Osaka"
+            ));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+use embedded_graphics::pixelcolor::Rgb888;
+
+use crate::font::Font;
+use crate::framebuffer::FrameBuffer;
+use crate::icons::{draw_weather_icon, ICON_SIZE};
+use crate::layout::{fit_pixel_size, Rect};
+use crate::locale::Locale;
+use crate::temperature::temperature_text;
+use crate::text::TextStyle;
+use crate::weather::Current;
+
+// アイコン・気温・天気の名前の間隔（pixel_size=1 のとき）
+const ICON_GAP: i32 = 4;
+
+// 現在の天気の表示の大きさ（pixel_size=1 のとき）
+pub fn current_unit_size(current: &Current, font: &Font, locale: Locale) -> (i32, i32) {
+    let temp = temperature_text(current.temp_c);
+    let name = locale.condition(current.icon);
+    let width = ICON_SIZE + ICON_GAP + font.text_width(&temp) + ICON_GAP + font.text_width(name);
+    (width, ICON_SIZE)
+}
+
+// 現在の天気（アイコン + 気温 + 天気の名前）を region 内に中央寄せで表示
+pub fn draw_current(
+    fb: &mut FrameBuffer,
+    region: &Rect,
+    current: &Current,
+    font: &Font,
+    locale: Locale,
+    color: Rgb888,
+) {
+    let temp = temperature_text(current.temp_c);
+    let name = locale.condition(current.icon);
+    let (unit_width, unit_height) = current_unit_size(current, font, locale);
+    let pixel_size = fit_pixel_size(unit_width, unit_height, region);
+    let style = TextStyle::new(font, pixel_size);
+
+    let x = region.centered_x(unit_width * pixel_size);
+    draw_weather_icon(fb, current.icon, x, region.y, pixel_size);
+
+    // 気温はアイコンの縦中央に揃える
+    let temp_x = x + (ICON_SIZE + ICON_GAP) * pixel_size;
+    let temp_y = region.y + (ICON_SIZE * pixel_size - style.height()) / 2;
+    style.draw_at(fb, &temp, temp_x, temp_y, color);
+
+    let name_x = temp_x + style.width(&temp) + ICON_GAP * pixel_size;
+    style.draw_at(fb, name, name_x, temp_y, color);
+}
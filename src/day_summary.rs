@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, Local, NaiveTime, Timelike};
+use embedded_graphics::pixelcolor::Rgb888;
+
+use crate::font::Font;
+use crate::framebuffer::FrameBuffer;
+use crate::layout::Rect;
+use crate::locale::Locale;
+use crate::render::Colors;
+use crate::temperature::temperature_text;
+use crate::text::{Align, TextStyle};
+use crate::weather::{DaySummary, Weather};
+
+// 日の入り時刻が不明なときに明日の表示へ切り替える時刻
+const EVENING_HOUR: u32 = 18;
+
+// 24時間表記に変換（日の出・日の入りがない日は "--:--"）
+fn format_astro_time(time: Option<NaiveTime>) -> String {
+    time.map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_else(|| "--:--".to_string())
+}
+
+// 表示する日を選ぶ。日の入り後は明日の概要にする
+fn select_day<'a>(weather: &'a Weather, now: &DateTime<Local>) -> Option<(bool, &'a DaySummary)> {
+    let today = weather.day(now.date_naive());
+    let evening = match today.and_then(|d| d.sunset) {
+        Some(sunset) => now.time() >= sunset,
+        None => now.hour() >= EVENING_HOUR,
+    };
+
+    if evening {
+        if let Some(tomorrow) = weather.day((*now + Duration::days(1)).date_naive()) {
+            return Some((true, tomorrow));
+        }
+    }
+    today.map(|d| (false, d))
+}
+
+// 表示する文字列と色の組。表示する日の予報がなければ None
+fn summary_segments(
+    weather: &Weather,
+    now: &DateTime<Local>,
+    colors: &Colors,
+    locale: Locale,
+) -> Option<Vec<(String, Rgb888)>> {
+    let (is_tomorrow, summary) = select_day(weather, now)?;
+    let labels = locale.summary_labels();
+
+    let label_color = colors.label;
+    let value_color = colors.value;
+
+    let mut segments = Vec::new();
+    if is_tomorrow {
+        segments.push((format!("{}  ", labels.tomorrow), value_color));
+    }
+    // 予報のない気温は "--" で表示
+    let temperature = |temp_c: Option<f64>| match temp_c {
+        Some(t) => (
+            format!("{}  ", temperature_text(t)),
+            colors.temperature_scale.color_for(t),
+        ),
+        None => ("--  ".to_string(), value_color),
+    };
+    segments.push((format!("{} ", labels.high), label_color));
+    segments.push(temperature(summary.maxtemp_c));
+    segments.push((format!("{} ", labels.low), label_color));
+    segments.push(temperature(summary.mintemp_c));
+    // 日の出・日の入りを提供しない取得元では省く
+    if summary.sunrise.is_some() || summary.sunset.is_some() {
+        segments.push((format!("{} ", labels.sunrise), label_color));
+        segments.push((
+            format!("{}  ", format_astro_time(summary.sunrise)),
+            value_color,
+        ));
+        segments.push((format!("{} ", labels.sunset), label_color));
+        segments.push((format_astro_time(summary.sunset), value_color));
+    }
+    // 末尾の区切りの空白は中央寄せの幅に含めない
+    if let Some((text, _)) = segments.last_mut() {
+        text.truncate(text.trim_end().len());
+    }
+    Some(segments)
+}
+
+// 今日の概要の大きさ（pixel_size=1 のとき）
+pub fn day_summary_unit_size(
+    weather: &Weather,
+    now: &DateTime<Local>,
+    colors: &Colors,
+    font: &Font,
+    locale: Locale,
+) -> (i32, i32) {
+    let segments = summary_segments(weather, now, colors, locale).unwrap_or_default();
+    let full_text: String = segments.iter().map(|(text, _)| text.as_str()).collect();
+    (font.text_width(&full_text), font.height)
+}
+
+// 最高・最低気温と日の出・日の入りを region 内に中央寄せで表示
+pub fn draw_day_summary(
+    fb: &mut FrameBuffer,
+    region: &Rect,
+    weather: &Weather,
+    now: &DateTime<Local>,
+    colors: &Colors,
+    font: &Font,
+    locale: Locale,
+) {
+    let Some(segments) = summary_segments(weather, now, colors, locale) else {
+        return;
+    };
+    let full_text: String = segments.iter().map(|(text, _)| text.as_str()).collect();
+    let spans: Vec<_> = segments
+        .iter()
+        .map(|(text, color)| (text.as_str(), *color))
+        .collect();
+    TextStyle::fit(font, &full_text, region)
+        .align(Align::Center)
+        .draw_spans(fb, &spans, region);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::{Current, WeatherIconType};
+    use chrono::{Datelike, NaiveDate, TimeZone};
+
+    fn day(date: u32, sunset: Option<&str>) -> DaySummary {
+        DaySummary {
+            date: NaiveDate::from_ymd_opt(2026, 1, date).unwrap(),
+            maxtemp_c: Some(date as f64),
+            mintemp_c: None,
+            sunrise: None,
+            sunset: sunset.and_then(|s| s.parse().ok()),
+        }
+    }
+
+    fn weather(days: Vec<DaySummary>) -> Weather {
+        Weather {
+            current: Current {
+                temp_c: 0.0,
+                icon: WeatherIconType::Sun,
+            },
+            hours: Vec::new(),
+            days,
+        }
+    }
+
+    // 2026-01-12 の hour:minute に選ばれる日（明日なら true）
+    fn selected(weather: &Weather, hour: u32, minute: u32) -> Option<(bool, u32)> {
+        let now = Local
+            .with_ymd_and_hms(2026, 1, 12, hour, minute, 0)
+            .unwrap();
+        select_day(weather, &now).map(|(tomorrow, d)| (tomorrow, d.date.day()))
+    }
+
+    #[test]
+    fn tomorrow_is_shown_after_sunset() {
+        let both = weather(vec![day(12, Some("16:49:00")), day(13, Some("16:50:00"))]);
+        assert_eq!(selected(&both, 16, 48), Some((false, 12)));
+        assert_eq!(selected(&both, 16, 49), Some((true, 13)));
+
+        // 日の入りがない日は EVENING_HOUR で切り替える
+        let no_sunset = weather(vec![day(12, None), day(13, None)]);
+        assert_eq!(selected(&no_sunset, 17, 59), Some((false, 12)));
+        assert_eq!(selected(&no_sunset, 18, 0), Some((true, 13)));
+
+        // 明日の予報がなければ今日のまま
+        let today_only = weather(vec![day(12, Some("16:49:00"))]);
+        assert_eq!(selected(&today_only, 20, 0), Some((false, 12)));
+        assert_eq!(selected(&weather(Vec::new()), 9, 30), None);
+    }
+}
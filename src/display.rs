@@ -0,0 +1,80 @@
+use minifb::{Key, Window, WindowOptions};
+use std::io;
+
+use crate::framebuffer::FrameBuffer;
+
+// 出力先からの入力イベント
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    // 終了要求（Esc キー、ウィンドウを閉じた等）
+    Quit,
+}
+
+// 描画結果の出力先（ウィンドウ、画像、フレームバッファデバイス等）
+pub trait DisplayBackend {
+    // 出力先の画面サイズ
+    fn size(&self) -> (usize, usize);
+
+    // FrameBuffer の内容を出力
+    fn present(&mut self, fb: &FrameBuffer) -> io::Result<()>;
+
+    // 前回呼び出し以降の入力イベントを取得
+    fn poll_events(&mut self) -> Vec<InputEvent> {
+        Vec::new()
+    }
+
+    // 出力先がまだ有効か
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+// minifb のウィンドウ出力
+pub struct MinifbDisplay {
+    window: Window,
+    width: usize,
+    height: usize,
+}
+
+impl MinifbDisplay {
+    pub fn new(
+        title: &str,
+        width: usize,
+        height: usize,
+        fps: usize,
+    ) -> Result<Self, minifb::Error> {
+        let mut window = Window::new(title, width, height, WindowOptions::default())?;
+        window.set_target_fps(fps);
+        Ok(Self {
+            window,
+            width,
+            height,
+        })
+    }
+}
+
+impl DisplayBackend for MinifbDisplay {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn present(&mut self, fb: &FrameBuffer) -> io::Result<()> {
+        self.window
+            .update_with_buffer(&fb.buffer, fb.width, fb.height)
+            .map_err(io::Error::other)
+    }
+
+    fn poll_events(&mut self) -> Vec<InputEvent> {
+        // 再描画しない間もウィンドウのイベントを処理する
+        self.window.update();
+        if self.window.is_key_down(Key::Escape) {
+            vec![InputEvent::Quit]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+}
@@ -0,0 +1,336 @@
+// Linux フレームバッファデバイス (/dev/fb0 など) への出力
+use memmap2::{MmapMut, MmapOptions};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use crate::display::DisplayBackend;
+use crate::framebuffer::FrameBuffer;
+
+const FBIOGET_VSCREENINFO: u32 = 0x4600;
+const FBIOGET_FSCREENINFO: u32 = 0x4602;
+
+// 1チャンネル分のビット配置 (linux/fb.h の fb_bitfield)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bitfield {
+    pub offset: u32,
+    pub length: u32,
+    pub msb_right: u32,
+}
+
+impl Bitfield {
+    pub const fn new(offset: u32, length: u32) -> Self {
+        Self {
+            offset,
+            length,
+            msb_right: 0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct VarScreenInfo {
+    xres: u32,
+    yres: u32,
+    xres_virtual: u32,
+    yres_virtual: u32,
+    xoffset: u32,
+    yoffset: u32,
+    bits_per_pixel: u32,
+    grayscale: u32,
+    red: Bitfield,
+    green: Bitfield,
+    blue: Bitfield,
+    transp: Bitfield,
+    nonstd: u32,
+    activate: u32,
+    height: u32,
+    width: u32,
+    accel_flags: u32,
+    pixclock: u32,
+    left_margin: u32,
+    right_margin: u32,
+    upper_margin: u32,
+    lower_margin: u32,
+    hsync_len: u32,
+    vsync_len: u32,
+    sync: u32,
+    vmode: u32,
+    rotate: u32,
+    colorspace: u32,
+    reserved: [u32; 4],
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct FixScreenInfo {
+    id: [u8; 16],
+    smem_start: libc::c_ulong,
+    smem_len: u32,
+    type_: u32,
+    type_aux: u32,
+    visual: u32,
+    xpanstep: u16,
+    ypanstep: u16,
+    ywrapstep: u16,
+    line_length: u32,
+    mmio_start: libc::c_ulong,
+    mmio_len: u32,
+    accel: u32,
+    capabilities: u16,
+    reserved: [u16; 2],
+}
+
+// デバイスの画素フォーマットとメモリ配置
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FbFormat {
+    pub width: usize,
+    pub height: usize,
+    pub bits_per_pixel: usize,
+    // 1行あたりのバイト数（パディングを含む）
+    pub stride: usize,
+    // 表示領域の開始位置（パン時のオフセット）
+    pub xoffset: usize,
+    pub yoffset: usize,
+    pub red: Bitfield,
+    pub green: Bitfield,
+    pub blue: Bitfield,
+}
+
+impl FbFormat {
+    // 16bpp RGB565
+    pub fn rgb565(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits_per_pixel: 16,
+            stride: width * 2,
+            xoffset: 0,
+            yoffset: 0,
+            red: Bitfield::new(11, 5),
+            green: Bitfield::new(5, 6),
+            blue: Bitfield::new(0, 5),
+        }
+    }
+
+    // 32bpp XRGB8888
+    pub fn xrgb8888(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits_per_pixel: 32,
+            stride: width * 4,
+            xoffset: 0,
+            yoffset: 0,
+            red: Bitfield::new(16, 8),
+            green: Bitfield::new(8, 8),
+            blue: Bitfield::new(0, 8),
+        }
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        self.bits_per_pixel / 8
+    }
+
+    fn offset(&self) -> usize {
+        self.yoffset * self.stride + self.xoffset * self.bytes_per_pixel()
+    }
+
+    // 表示に必要なメモリサイズ
+    fn len(&self) -> usize {
+        self.offset() + self.height * self.stride
+    }
+
+    // 0x00RRGGBB をデバイスの画素値に変換
+    fn pack(&self, color: u32) -> u32 {
+        let channel = |value: u32, field: &Bitfield| -> u32 {
+            if field.length == 0 {
+                return 0;
+            }
+            (value >> (8 - field.length.min(8))) << field.offset
+        };
+        channel((color >> 16) & 0xFF, &self.red)
+            | channel((color >> 8) & 0xFF, &self.green)
+            | channel(color & 0xFF, &self.blue)
+    }
+
+    fn validate(&self) -> io::Result<()> {
+        if !matches!(self.bits_per_pixel, 16 | 24 | 32) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("未対応のビット深度です: {}bpp", self.bits_per_pixel),
+            ));
+        }
+        if self.stride < self.width * self.bytes_per_pixel() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("行のバイト数が幅に対して小さすぎます: {}", self.stride),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub struct FbDev {
+    // mmap の寿命の間ファイルを開いたままにする
+    _file: File,
+    map: MmapMut,
+    format: FbFormat,
+}
+
+impl FbDev {
+    // デバイスを開き、ioctl で画面情報を取得
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let format = query_format(&file)?;
+        Self::map(file, format)
+    }
+
+    // 画面情報を指定して開く（通常ファイルをデバイス代わりに使う場合など）
+    pub fn open_with_format(path: &Path, format: FbFormat) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Self::map(file, format)
+    }
+
+    fn map(file: File, format: FbFormat) -> io::Result<Self> {
+        format.validate()?;
+        let len = format.len();
+        if file.metadata()?.is_file() && file.metadata()?.len() < len as u64 {
+            file.set_len(len as u64)?;
+        }
+        let map = unsafe { MmapOptions::new().len(len).map_mut(&file)? };
+        Ok(Self {
+            _file: file,
+            map,
+            format,
+        })
+    }
+}
+
+impl DisplayBackend for FbDev {
+    fn size(&self) -> (usize, usize) {
+        (self.format.width, self.format.height)
+    }
+
+    // FrameBuffer の内容をデバイスへ転送（はみ出す部分は切り捨て）
+    fn present(&mut self, fb: &FrameBuffer) -> io::Result<()> {
+        let format = self.format;
+        let bpp = format.bytes_per_pixel();
+        let width = fb.width.min(format.width);
+        let height = fb.height.min(format.height);
+        let base = format.offset();
+
+        for y in 0..height {
+            let src = &fb.buffer[y * fb.width..y * fb.width + width];
+            let row_start = base + y * format.stride;
+            let dst = &mut self.map[row_start..row_start + width * bpp];
+            for (pixel, &color) in dst.chunks_exact_mut(bpp).zip(src) {
+                let value = format.pack(color).to_le_bytes();
+                pixel.copy_from_slice(&value[..bpp]);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn query_format(file: &File) -> io::Result<FbFormat> {
+    let mut var = VarScreenInfo::default();
+    let mut fix = FixScreenInfo::default();
+    let fd = file.as_raw_fd();
+    unsafe {
+        if libc::ioctl(fd, FBIOGET_VSCREENINFO as _, &mut var) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::ioctl(fd, FBIOGET_FSCREENINFO as _, &mut fix) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(FbFormat {
+        width: var.xres as usize,
+        height: var.yres as usize,
+        bits_per_pixel: var.bits_per_pixel as usize,
+        stride: fix.line_length as usize,
+        xoffset: var.xoffset as usize,
+        yoffset: var.yoffset as usize,
+        red: var.red,
+        green: var.green,
+        blue: var.blue,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("home_clock_{}_{}", std::process::id(), name))
+    }
+
+    fn test_frame() -> FrameBuffer {
+        let mut fb = FrameBuffer::new(2, 2);
+        fb.buffer
+            .copy_from_slice(&[0xFF0000, 0x00FF00, 0x0000FF, 0xFFFFFF]);
+        fb
+    }
+
+    #[test]
+    fn writes_xrgb8888_with_stride_padding() {
+        let path = temp_path("fb32");
+        File::create(&path).unwrap();
+        let mut format = FbFormat::xrgb8888(2, 2);
+        format.stride = 12;
+
+        let mut dev = FbDev::open_with_format(&path, format).unwrap();
+        dev.present(&test_frame()).unwrap();
+        drop(dev);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(
+            &bytes[0..8],
+            &[0x00, 0x00, 0xFF, 0x00, 0x00, 0xFF, 0x00, 0x00]
+        );
+        assert_eq!(&bytes[8..12], &[0; 4]);
+        assert_eq!(
+            &bytes[12..20],
+            &[0xFF, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00]
+        );
+    }
+
+    #[test]
+    fn writes_rgb565() {
+        let path = temp_path("fb16");
+        File::create(&path).unwrap();
+
+        let mut dev = FbDev::open_with_format(&path, FbFormat::rgb565(2, 2)).unwrap();
+        dev.present(&test_frame()).unwrap();
+        drop(dev);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let pixels: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(pixels, vec![0xF800, 0x07E0, 0x001F, 0xFFFF]);
+    }
+
+    #[test]
+    fn clips_larger_frame_to_device() {
+        let path = temp_path("fbclip");
+        File::create(&path).unwrap();
+
+        let mut dev = FbDev::open_with_format(&path, FbFormat::rgb565(1, 1)).unwrap();
+        dev.present(&test_frame()).unwrap();
+        drop(dev);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bytes, vec![0x00, 0xF8]);
+    }
+}
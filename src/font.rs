@@ -4,12 +4,98 @@ use embedded_graphics::{
     primitives::{PrimitiveStyle, Rectangle},
 };
 
-use crate::framebuffer::FrameBuffer;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, OnceLock};
+
+use crate::bdf;
 
 pub const DIGIT_WIDTH: usize = 8;
 pub const DIGIT_HEIGHT: usize = 12;
 
-pub const DIGITS: [[u8; DIGIT_HEIGHT]; 10] = [
+// 印字可能な ASCII (' '〜'~') の 8x12 グリフ（幅が 8 未満の文字は左詰め、幅は narrow_width）
+// 小文字は x ハイト 8。下に伸びる g, p, q, y は x ハイトの中に収める
+const ASCII: [[u8; DIGIT_HEIGHT]; 95] = [
+    // 空白
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // !
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000,
+        0b11000000, 0b00000000, 0b00000000, 0b11000000, 0b11000000,
+    ],
+    // "
+    [
+        0b11001100, 0b11001100, 0b11001100, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // #
+    [
+        0b00110011, 0b00110011, 0b11111111, 0b11111111, 0b01100110, 0b01100110, 0b01100110,
+        0b01100110, 0b11111111, 0b11111111, 0b11001100, 0b11001100,
+    ],
+    // $
+    [
+        0b00011000, 0b01111110, 0b11011011, 0b11011000, 0b11011000, 0b01111110, 0b00011011,
+        0b00011011, 0b11011011, 0b01111110, 0b00011000, 0b00011000,
+    ],
+    // %
+    [
+        0b11100000, 0b11100000, 0b11100010, 0b00000100, 0b00001000, 0b00010000, 0b00100000,
+        0b01000000, 0b10000000, 0b00000111, 0b00000111, 0b00000111,
+    ],
+    // &
+    [
+        0b01111000, 0b11001100, 0b11001100, 0b01111000, 0b01110000, 0b11011011, 0b11001110,
+        0b11000110, 0b11001110, 0b11011011, 0b01110011, 0b00000000,
+    ],
+    // '
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // (
+    [
+        0b00011000, 0b00110000, 0b01100000, 0b01100000, 0b11000000, 0b11000000, 0b11000000,
+        0b11000000, 0b01100000, 0b01100000, 0b00110000, 0b00011000,
+    ],
+    // )
+    [
+        0b11000000, 0b01100000, 0b00110000, 0b00110000, 0b00011000, 0b00011000, 0b00011000,
+        0b00011000, 0b00110000, 0b00110000, 0b01100000, 0b11000000,
+    ],
+    // *
+    [
+        0b00000000, 0b00000000, 0b00011000, 0b11011011, 0b01111110, 0b00111100, 0b01111110,
+        0b11011011, 0b00011000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // +
+    [
+        0b00000000, 0b00000000, 0b00011000, 0b00011000, 0b00011000, 0b11111111, 0b11111111,
+        0b00011000, 0b00011000, 0b00011000, 0b00000000, 0b00000000,
+    ],
+    // ,
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b01100000, 0b01100000, 0b11000000,
+    ],
+    // -
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b01111110,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // .
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b11000000, 0b11000000,
+    ],
+    // /
+    [
+        0b00000011, 0b00000011, 0b00000110, 0b00000110, 0b00001100, 0b00001100, 0b00011000,
+        0b00011000, 0b00110000, 0b00110000, 0b01100000, 0b11000000,
+    ],
     // 0
     [
         0b01111110, 0b11111111, 0b11000011, 0b11000011, 0b11000011, 0b11000011, 0b11000011,
@@ -60,18 +146,41 @@ pub const DIGITS: [[u8; DIGIT_HEIGHT]; 10] = [
         0b01111110, 0b11111111, 0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b01111111,
         0b00000011, 0b00000011, 0b11000011, 0b11111111, 0b01111110,
     ],
-];
-
-pub const COLON: [u8; DIGIT_HEIGHT] = [
-    0b00, 0b00, 0b00, 0b11, 0b11, 0b00, 0b00, 0b11, 0b11, 0b00, 0b00, 0b00,
-];
-
-pub const HYPHEN: [u8; DIGIT_HEIGHT] = [
-    0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b01111110, 0b00000000,
-    0b00000000, 0b00000000, 0b00000000, 0b00000000,
-];
-
-pub const LETTERS: [[u8; DIGIT_HEIGHT]; 26] = [
+    // :
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b11000000, 0b11000000, 0b00000000, 0b00000000,
+        0b11000000, 0b11000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // ;
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b01100000, 0b01100000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b01100000, 0b01100000, 0b11000000,
+    ],
+    // <
+    [
+        0b00000000, 0b00000110, 0b00001100, 0b00011000, 0b00110000, 0b01100000, 0b01100000,
+        0b00110000, 0b00011000, 0b00001100, 0b00000110, 0b00000000,
+    ],
+    // =
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b11111111, 0b11111111, 0b00000000, 0b00000000,
+        0b11111111, 0b11111111, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // >
+    [
+        0b00000000, 0b01100000, 0b00110000, 0b00011000, 0b00001100, 0b00000110, 0b00000110,
+        0b00001100, 0b00011000, 0b00110000, 0b01100000, 0b00000000,
+    ],
+    // ?
+    [
+        0b01111110, 0b11111111, 0b11000011, 0b00000011, 0b00000110, 0b00001100, 0b00011000,
+        0b00011000, 0b00000000, 0b00000000, 0b00011000, 0b00011000,
+    ],
+    // @
+    [
+        0b01111110, 0b11000011, 0b11000011, 0b11011110, 0b11011011, 0b11011011, 0b11011011,
+        0b11011110, 0b11000000, 0b11000000, 0b11000011, 0b01111110,
+    ],
     // A
     [
         0b00111100, 0b01111110, 0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b11111111,
@@ -202,93 +311,430 @@ pub const LETTERS: [[u8; DIGIT_HEIGHT]; 26] = [
         0b11111111, 0b11111111, 0b00000011, 0b00000110, 0b00001100, 0b00011000, 0b00110000,
         0b01100000, 0b11000000, 0b11000000, 0b11111111, 0b11111111,
     ],
+    // [
+    [
+        0b11111000, 0b11111000, 0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000,
+        0b11000000, 0b11000000, 0b11000000, 0b11111000, 0b11111000,
+    ],
+    // \
+    [
+        0b11000000, 0b11000000, 0b01100000, 0b01100000, 0b00110000, 0b00110000, 0b00011000,
+        0b00011000, 0b00001100, 0b00001100, 0b00000110, 0b00000011,
+    ],
+    // ]
+    [
+        0b11111000, 0b11111000, 0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000,
+        0b00011000, 0b00011000, 0b00011000, 0b11111000, 0b11111000,
+    ],
+    // ^
+    [
+        0b00011000, 0b00111100, 0b01100110, 0b11000011, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // _
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b11111111, 0b11111111,
+    ],
+    // `
+    [
+        0b11000000, 0b01100000, 0b00110000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
+    // a
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b01111111, 0b00000011,
+        0b01111111, 0b11111111, 0b11000011, 0b11111111, 0b01111111,
+    ],
+    // b
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11111110, 0b11111111, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b11111110,
+    ],
+    // c
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b11111111, 0b11000000,
+        0b11000000, 0b11000000, 0b11000000, 0b11111111, 0b01111110,
+    ],
+    // d
+    [
+        0b00000011, 0b00000011, 0b00000011, 0b00000011, 0b01111111, 0b11111111, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b01111111,
+    ],
+    // e
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b11111111, 0b11000011,
+        0b11111111, 0b11111111, 0b11000000, 0b11111111, 0b01111110,
+    ],
+    // f
+    [
+        0b00011110, 0b00111111, 0b00110000, 0b00110000, 0b11111111, 0b11111111, 0b00110000,
+        0b00110000, 0b00110000, 0b00110000, 0b00110000, 0b00110000,
+    ],
+    // g
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111111, 0b11111111, 0b11000011,
+        0b11111111, 0b01111111, 0b00000011, 0b11111111, 0b11111110,
+    ],
+    // h
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11111110, 0b11111111, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11000011, 0b11000011,
+    ],
+    // i
+    [
+        0b00011000, 0b00011000, 0b00000000, 0b00000000, 0b01111000, 0b01111000, 0b00011000,
+        0b00011000, 0b00011000, 0b00011000, 0b01111110, 0b01111110,
+    ],
+    // j
+    [
+        0b00000110, 0b00000110, 0b00000000, 0b00001110, 0b00001110, 0b00000110, 0b00000110,
+        0b00000110, 0b11000110, 0b11000110, 0b11111100, 0b01111000,
+    ],
+    // k
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000110, 0b11001100, 0b11011000,
+        0b11110000, 0b11110000, 0b11011000, 0b11001100, 0b11000110,
+    ],
+    // l
+    [
+        0b01111000, 0b01111000, 0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000,
+        0b00011000, 0b00011000, 0b00011000, 0b01111110, 0b01111110,
+    ],
+    // m
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11101110, 0b11111111, 0b11011011,
+        0b11011011, 0b11011011, 0b11011011, 0b11011011, 0b11011011,
+    ],
+    // n
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11111110, 0b11111111, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11000011, 0b11000011,
+    ],
+    // o
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111110, 0b11111111, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b01111110,
+    ],
+    // p
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11111110, 0b11111111, 0b11000011,
+        0b11111111, 0b11111110, 0b11000000, 0b11000000, 0b11000000,
+    ],
+    // q
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111111, 0b11111111, 0b11000011,
+        0b11111111, 0b01111111, 0b00000011, 0b00000011, 0b00000011,
+    ],
+    // r
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11011110, 0b11111111, 0b11100000,
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000,
+    ],
+    // s
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01111111, 0b11111111, 0b11000000,
+        0b11111110, 0b01111111, 0b00000011, 0b11111111, 0b11111110,
+    ],
+    // t
+    [
+        0b00110000, 0b00110000, 0b00110000, 0b00110000, 0b11111111, 0b11111111, 0b00110000,
+        0b00110000, 0b00110000, 0b00110000, 0b00111111, 0b00011111,
+    ],
+    // u
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11000011, 0b11000011, 0b11000011,
+        0b11000011, 0b11000011, 0b11000011, 0b11111111, 0b01111111,
+    ],
+    // v
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11000011, 0b11000011, 0b11000011,
+        0b11000011, 0b01100110, 0b01100110, 0b00111100, 0b00011000,
+    ],
+    // w
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11011011, 0b11011011, 0b11011011,
+        0b11011011, 0b11011011, 0b11011011, 0b11111111, 0b01100110,
+    ],
+    // x
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11000011, 0b01100110, 0b00111100,
+        0b00011000, 0b00111100, 0b01100110, 0b11000011, 0b11000011,
+    ],
+    // y
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11000011, 0b11000011, 0b11000011,
+        0b11111111, 0b01111111, 0b00000011, 0b11111111, 0b11111110,
+    ],
+    // z
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b11111111, 0b11111111, 0b00000110,
+        0b00001100, 0b00011000, 0b00110000, 0b11111111, 0b11111111,
+    ],
+    // {
+    [
+        0b00011100, 0b00110000, 0b00110000, 0b00110000, 0b00110000, 0b11000000, 0b11000000,
+        0b00110000, 0b00110000, 0b00110000, 0b00110000, 0b00011100,
+    ],
+    // |
+    [
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000,
+        0b11000000, 0b11000000, 0b11000000, 0b11000000, 0b11000000,
+    ],
+    // }
+    [
+        0b11100000, 0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00001100, 0b00001100,
+        0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b11100000,
+    ],
+    // ~
+    [
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b01110011, 0b11011110, 0b11001110,
+        0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+    ],
 ];
 
-fn draw_pixel_block(fb: &mut FrameBuffer, x: i32, y: i32, size: i32, color: Rgb888) {
-    Rectangle::new(Point::new(x, y), Size::new(size as u32, size as u32))
-        .into_styled(PrimitiveStyle::with_fill(color))
-        .draw(fb)
-        .unwrap();
+// ASCII 以外の記号（ASCII と同じく左詰め）
+const SYMBOLS: [(char, [u8; DIGIT_HEIGHT]); 5] = [
+    (
+        '°',
+        [
+            0b01110000, 0b11011000, 0b11011000, 0b01110000, 0b00000000, 0b00000000, 0b00000000,
+            0b00000000, 0b00000000, 0b00000000, 0b00000000, 0b00000000,
+        ],
+    ),
+    (
+        '←',
+        [
+            0b00000000, 0b00000000, 0b00011000, 0b00110000, 0b01100000, 0b11111111, 0b11111111,
+            0b01100000, 0b00110000, 0b00011000, 0b00000000, 0b00000000,
+        ],
+    ),
+    (
+        '→',
+        [
+            0b00000000, 0b00000000, 0b00011000, 0b00001100, 0b00000110, 0b11111111, 0b11111111,
+            0b00000110, 0b00001100, 0b00011000, 0b00000000, 0b00000000,
+        ],
+    ),
+    (
+        '↑',
+        [
+            0b00011000, 0b00111100, 0b01111110, 0b11011011, 0b00011000, 0b00011000, 0b00011000,
+            0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000,
+        ],
+    ),
+    (
+        '↓',
+        [
+            0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000, 0b00011000,
+            0b00011000, 0b11011011, 0b01111110, 0b00111100, 0b00011000,
+        ],
+    ),
+];
+
+// 日本語表示に使う文字（幅 12 x 高さ 12）。表示する文字だけを収録した BDF
+const JAPANESE_BDF: &str = include_str!("../assets/fonts/ja.bdf");
+
+// 1 文字分のビットマップ
+#[derive(Clone, Debug)]
+pub struct Glyph {
+    pub width: usize,
+    pub height: usize,
+    // 描画位置のずれ（x は送りの起点から右へ、y は行の上端から下へ）
+    pub x_offset: i32,
+    pub y_offset: i32,
+    // 次の文字までの送り幅
+    pub advance: i32,
+    // 各行の下位 width ビットを左から使う
+    rows: Vec<u32>,
 }
 
-pub fn draw_digit(fb: &mut FrameBuffer, digit: u8, x: i32, y: i32, pixel_size: i32, color: Rgb888) {
-    let bitmap = &DIGITS[digit as usize];
-    for (row, &bits) in bitmap.iter().enumerate() {
-        for col in 0..DIGIT_WIDTH {
-            if (bits >> (DIGIT_WIDTH - 1 - col)) & 1 == 1 {
-                draw_pixel_block(
-                    fb,
-                    x + (col as i32) * pixel_size,
-                    y + (row as i32) * pixel_size,
-                    pixel_size,
-                    color,
-                );
-            }
+impl Glyph {
+    pub fn new(width: usize, rows: Vec<u32>, x_offset: i32, y_offset: i32, advance: i32) -> Self {
+        Self {
+            width,
+            height: rows.len(),
+            x_offset,
+            y_offset,
+            advance,
+            rows,
         }
     }
+
+    // 内蔵フォントの文字。文字間は 1 空ける
+    fn builtin(width: usize, rows: impl IntoIterator<Item = u32>) -> Self {
+        Self::new(width, rows.into_iter().collect(), 0, 0, width as i32 + 1)
+    }
+
+    fn is_set(&self, col: usize, row: usize) -> bool {
+        (self.rows[row] >> (self.width - 1 - col)) & 1 == 1
+    }
+
+    // 文字列の末尾に置いたときの右端
+    fn extent(&self) -> i32 {
+        self.x_offset + self.width as i32
+    }
 }
 
-pub fn draw_colon(
-    fb: &mut FrameBuffer,
-    x: i32,
-    y: i32,
-    pixel_size: i32,
-    color: Rgb888,
-    visible: bool,
-) {
-    if !visible {
-        return;
+// 8x12 の表のうち幅の狭い文字
+fn narrow_width(ch: char) -> usize {
+    match ch {
+        ':' | '.' | '!' | '\'' | '|' => 2,
+        ',' | ';' => 3,
+        ' ' | '`' => 4,
+        '(' | ')' | '[' | ']' | '°' => 5,
+        '"' | '{' | '}' => 6,
+        _ => DIGIT_WIDTH,
     }
-    for (row, &bits) in COLON.iter().enumerate() {
-        for col in 0..2 {
-            if (bits >> (1 - col)) & 1 == 1 {
-                draw_pixel_block(
-                    fb,
-                    x + col * pixel_size,
-                    y + (row as i32) * pixel_size,
-                    pixel_size,
-                    color,
-                );
-            }
-        }
+}
+
+// 左詰めの 8 ビットの表から作る
+fn table_glyph(ch: char, rows: &[u8; DIGIT_HEIGHT]) -> Glyph {
+    let width = narrow_width(ch);
+    Glyph::builtin(
+        width,
+        rows.iter()
+            .map(|&bits| bits as u32 >> (DIGIT_WIDTH - width)),
+    )
+}
+
+// 文字の集合。行の高さとベースラインの位置（上端から）を持つ
+#[derive(Clone, Debug)]
+pub struct Font {
+    pub height: i32,
+    pub ascent: i32,
+    glyphs: Arc<HashMap<char, Glyph>>,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self::builtin().clone()
     }
 }
 
-pub fn draw_hyphen(fb: &mut FrameBuffer, x: i32, y: i32, pixel_size: i32, color: Rgb888) {
-    for (row, &bits) in HYPHEN.iter().enumerate() {
-        for col in 0..DIGIT_WIDTH {
-            if (bits >> (DIGIT_WIDTH - 1 - col)) & 1 == 1 {
-                draw_pixel_block(
-                    fb,
-                    x + (col as i32) * pixel_size,
-                    y + (row as i32) * pixel_size,
-                    pixel_size,
-                    color,
-                );
+impl Font {
+    pub fn new(height: i32, ascent: i32, glyphs: HashMap<char, Glyph>) -> Self {
+        Self {
+            height,
+            ascent,
+            glyphs: Arc::new(glyphs),
+        }
+    }
+
+    // 上の表と日本語の BDF から作る内蔵フォント（高さ 12、ベースラインは下端）
+    pub fn builtin() -> &'static Font {
+        static BUILTIN: OnceLock<Font> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut glyphs = HashMap::new();
+            for (ch, rows) in (' '..='~').zip(&ASCII) {
+                glyphs.insert(ch, table_glyph(ch, rows));
+            }
+            for (ch, rows) in &SYMBOLS {
+                glyphs.insert(*ch, table_glyph(*ch, rows));
             }
+            let japanese = bdf::parse(JAPANESE_BDF).expect("内蔵の日本語フォントが不正です");
+            glyphs.extend(
+                japanese
+                    .glyphs
+                    .iter()
+                    .map(|(ch, glyph)| (*ch, glyph.clone())),
+            );
+            let height = DIGIT_HEIGHT as i32;
+            Font::new(height, height, glyphs)
+        })
+    }
+
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn glyph(&self, ch: char) -> Option<&Glyph> {
+        self.glyphs.get(&ch)
+    }
+
+    // 描画に使う文字と、そのフォントのベースラインの位置
+    // このフォントにない文字は内蔵フォント、どちらにもない文字は "?" で表示する
+    fn resolve(&self, ch: char) -> (&Glyph, i32) {
+        let builtin = Font::builtin();
+        if let Some(glyph) = self.glyph(ch) {
+            return (glyph, self.ascent);
+        }
+        if let Some(glyph) = builtin.glyph(ch) {
+            return (glyph, builtin.ascent);
+        }
+        match self.glyph('?') {
+            Some(glyph) => (glyph, self.ascent),
+            None => (&builtin.glyphs[&'?'], builtin.ascent),
         }
     }
+
+    // 文字列の幅（pixel_size=1 のとき）
+    pub fn text_width(&self, text: &str) -> i32 {
+        let mut width = 0;
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            let (glyph, _) = self.resolve(ch);
+            width += if chars.peek().is_some() {
+                glyph.advance
+            } else {
+                glyph.extent()
+            };
+        }
+        width
+    }
+
+    // 続けて描画するときの次の文字までの送り幅（pixel_size=1 のとき）
+    pub fn advance(&self, text: &str) -> i32 {
+        text.chars().map(|ch| self.resolve(ch).0.advance).sum()
+    }
+
+    // 文字列を描画（y は行の上端）。target は切り抜いた描画先でもよい
+    pub fn draw_text<D: Canvas>(
+        &self,
+        target: &mut D,
+        text: &str,
+        x: i32,
+        y: i32,
+        pixel_size: i32,
+        color: Rgb888,
+    ) {
+        let mut current_x = x;
+        for ch in text.chars() {
+            let (glyph, ascent) = self.resolve(ch);
+            // 別のフォントの文字もベースラインを揃える
+            let top = y + (self.ascent - ascent + glyph.y_offset) * pixel_size;
+            let left = current_x + glyph.x_offset * pixel_size;
+            draw_glyph(target, glyph, left, top, pixel_size, color);
+            current_x += glyph.advance * pixel_size;
+        }
+    }
+}
+
+// 文字を描画できる先（FrameBuffer とその切り抜き）
+pub trait Canvas: DrawTarget<Color = Rgb888, Error = Infallible> {}
+
+impl<D: DrawTarget<Color = Rgb888, Error = Infallible>> Canvas for D {}
+
+fn draw_pixel_block<D: Canvas>(target: &mut D, x: i32, y: i32, size: i32, color: Rgb888) {
+    Rectangle::new(Point::new(x, y), Size::new(size as u32, size as u32))
+        .into_styled(PrimitiveStyle::with_fill(color))
+        .draw(target)
+        .unwrap();
 }
 
-pub fn draw_letter(
-    fb: &mut FrameBuffer,
-    letter: char,
+fn draw_glyph<D: Canvas>(
+    target: &mut D,
+    glyph: &Glyph,
     x: i32,
     y: i32,
     pixel_size: i32,
     color: Rgb888,
 ) {
-    let index = match letter {
-        'A'..='Z' => (letter as usize) - ('A' as usize),
-        'a'..='z' => (letter as usize) - ('a' as usize),
-        _ => return,
-    };
-    let bitmap = &LETTERS[index];
-    for (row, &bits) in bitmap.iter().enumerate() {
-        for col in 0..DIGIT_WIDTH {
-            if (bits >> (DIGIT_WIDTH - 1 - col)) & 1 == 1 {
+    for row in 0..glyph.height {
+        for col in 0..glyph.width {
+            if glyph.is_set(col, row) {
                 draw_pixel_block(
-                    fb,
+                    target,
                     x + (col as i32) * pixel_size,
                     y + (row as i32) * pixel_size,
                     pixel_size,
@@ -298,13 +744,3 @@ pub fn draw_letter(
         }
     }
 }
-
-pub fn draw_text(fb: &mut FrameBuffer, text: &str, x: i32, y: i32, pixel_size: i32, color: Rgb888) {
-    let letter_width = DIGIT_WIDTH as i32 * pixel_size;
-    let spacing = pixel_size;
-    let mut current_x = x;
-    for ch in text.chars() {
-        draw_letter(fb, ch, current_x, y, pixel_size, color);
-        current_x += letter_width + spacing;
-    }
-}
@@ -1,150 +1,84 @@
-use embedded_graphics::pixelcolor::Rgb888;
+use chrono::Timelike;
 
-use crate::font::{draw_colon, draw_digit, DIGIT_WIDTH};
+use crate::font::Font;
 use crate::framebuffer::FrameBuffer;
-use crate::weather::{draw_weather_icon, get_weather_icon, Hour};
-
-// 予報1件分を描画（item_widthを渡して中央寄せ計算）
-fn draw_forecast_item(fb: &mut FrameBuffer, hour: &Hour, center_x: i32, y: i32) {
-    let pixel_size = 2;
-    let digit_width = DIGIT_WIDTH as i32 * pixel_size;
-    let colon_width = 2 * pixel_size;
-    let spacing = pixel_size;
-
-    // 時間を抽出 (例: "2024-01-12 22:00" -> hour=22, minute=00)
-    let time_str = &hour.time;
-    let hour_val: u8 = time_str
-        .split(' ')
-        .nth(1)
-        .and_then(|t| t.split(':').next())
-        .and_then(|h| h.parse().ok())
-        .unwrap_or(0);
-
-    let color = Rgb888::new(150, 150, 150);
-
-    // 時間表示 (HH:00) の幅を計算して中央寄せ
-    // HH:00 = digit + spacing + digit + spacing + colon + spacing + digit + spacing + digit
-    let time_width = digit_width * 4 + colon_width + spacing * 4;
-    let time_x = center_x - time_width / 2;
-
-    let mut x = time_x;
-    draw_digit(fb, hour_val / 10, x, y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, hour_val % 10, x, y, pixel_size, color);
-    x += digit_width + spacing;
-
-    // コロン
-    draw_colon(fb, x, y, pixel_size, color, true);
-    x += colon_width + spacing;
-
-    // 分 (00)
-    draw_digit(fb, 0, x, y, pixel_size, color);
-    x += digit_width + spacing;
-    draw_digit(fb, 0, x, y, pixel_size, color);
+use crate::icons::{draw_weather_icon, ICON_SIZE};
+use crate::layout::{fit_pixel_size, Rect};
+use crate::render::Theme;
+use crate::temperature::temperature_text;
+use crate::text::{Align, TextStyle};
+use crate::weather::Hour;
+
+// 表示する予報の件数
+pub const MAX_ITEMS: usize = 4;
+
+// 予報1件分の幅（pixel_size=1 のとき）
+const ITEM_UNIT_WIDTH: i32 = 90;
+
+// 予報全体の大きさ（pixel_size=1 のとき）
+// 高さは 時刻 + 余白 3 + アイコン 16 + 余白 3 + 気温 + 余白 2 + 降水確率
+pub fn forecast_unit_size(font: &Font) -> (i32, i32) {
+    (
+        ITEM_UNIT_WIDTH * MAX_ITEMS as i32,
+        font.height * 3 + ICON_SIZE + 8,
+    )
+}
 
-    // アイコン表示（中央寄せ）
-    let icon = get_weather_icon(hour.condition.code, hour.is_day != 0);
-    let icon_size = 16 * 2; // 16px * scale 2
-    let icon_x = center_x - icon_size / 2;
-    let icon_y = y + 30;
-    draw_weather_icon(fb, icon, icon_x, icon_y, 2);
+// 予報1件分を item の中に中央寄せで描画
+fn draw_forecast_item(
+    fb: &mut FrameBuffer,
+    hour: &Hour,
+    item: &Rect,
+    pixel_size: i32,
+    theme: &Theme,
+) {
+    let colors = &theme.colors;
+    let style = TextStyle::new(&theme.font, pixel_size).align(Align::Center);
+    let line = |y: i32| Rect::new(item.x, y, item.width, style.height());
+
+    // 時刻表示 ("09:00" など)
+    let label = theme.time_format.hour_label(hour.time.hour() as u8);
+    style.draw(fb, &label, &line(item.y), colors.forecast_time);
+
+    // アイコン表示
+    let icon_x = item.centered_x(ICON_SIZE * pixel_size);
+    let icon_y = item.y + style.height() + 3 * pixel_size;
+    draw_weather_icon(fb, hour.icon, icon_x, icon_y, pixel_size);
+
+    // 気温表示（気温に応じた色）。気温のない予報は空けておく
+    let temp_y = icon_y + (ICON_SIZE + 3) * pixel_size;
+    if let Some(temp_c) = hour.temp_c {
+        let temp_color = colors.temperature_scale.color_for(temp_c);
+        style.draw(fb, &temperature_text(temp_c), &line(temp_y), temp_color);
+    }
 
-    // 降水確率表示（中央寄せ）
-    let rain_y = icon_y + 40;
+    // 降水確率表示
+    let rain_y = temp_y + style.height() + 2 * pixel_size;
     let rain_color = if hour.chance_of_rain >= 50 {
-        Rgb888::new(100, 150, 255)
+        colors.rain_likely
     } else {
-        Rgb888::new(120, 120, 120)
+        colors.rain
     };
-
-    let rain = hour.chance_of_rain;
-
-    // 降水確率の幅を計算（数字 + spacing + %記号）
-    // 時間表示と同じ形式: 要素N個の間にspacing (N-1)個 → 要素間すべてにspacing
-    let rain_digits = if rain >= 100 {
-        3
-    } else if rain >= 10 {
-        2
-    } else {
-        1
-    };
-    let percent_width = digit_width; // %記号の幅
-    let rain_width = digit_width * rain_digits + percent_width + spacing * rain_digits;
-    let rain_x = center_x - rain_width / 2;
-
-    let mut rx = rain_x;
-    if rain >= 100 {
-        draw_digit(fb, 1, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width + spacing;
-        draw_digit(fb, 0, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width + spacing;
-        draw_digit(fb, 0, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width;
-    } else if rain >= 10 {
-        draw_digit(fb, rain / 10, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width + spacing;
-        draw_digit(fb, rain % 10, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width;
-    } else {
-        draw_digit(fb, rain, rx, rain_y, pixel_size, rain_color);
-        rx += digit_width;
-    }
-
-    // % 記号（数字と同じ高さ、spacingを挟む）
-    rx += pixel_size * 2;
-    draw_percent(fb, rx, rain_y, pixel_size, rain_color);
-}
-
-// %記号を描画（数字と同じサイズ: 幅8 x 高さ12）
-fn draw_percent(fb: &mut FrameBuffer, x: i32, y: i32, pixel_size: i32, color: Rgb888) {
-    use embedded_graphics::{
-        prelude::*,
-        primitives::{PrimitiveStyle, Rectangle},
-    };
-
-    let ps = pixel_size;
-
-    // 上の丸 (3x3, 行0-2, 列0-2)
-    Rectangle::new(
-        Point::new(x, y),
-        embedded_graphics::geometry::Size::new(ps as u32 * 3, ps as u32 * 3),
-    )
-    .into_styled(PrimitiveStyle::with_fill(color))
-    .draw(fb)
-    .unwrap();
-
-    // 斜線 (行2-9, 列6から列1へ、右上から左下へ)
-    for i in 0..8 {
-        Rectangle::new(
-            Point::new(x + (6 - i) * ps, y + (i + 2) * ps),
-            embedded_graphics::geometry::Size::new(ps as u32, ps as u32),
-        )
-        .into_styled(PrimitiveStyle::with_fill(color))
-        .draw(fb)
-        .unwrap();
-    }
-
-    // 下の丸 (3x3, 行9-11, 列5-7)
-    Rectangle::new(
-        Point::new(x + ps * 5, y + ps * 9),
-        embedded_graphics::geometry::Size::new(ps as u32 * 3, ps as u32 * 3),
-    )
-    .into_styled(PrimitiveStyle::with_fill(color))
-    .draw(fb)
-    .unwrap();
+    let rain = format!("{}%", hour.chance_of_rain);
+    style.draw(fb, &rain, &line(rain_y), rain_color);
 }
 
-// 4時間分の予報を画面下部に表示
-pub fn draw_forecast(fb: &mut FrameBuffer, forecast: &[Hour]) {
-    let start_y = 360;
-    let item_width = 180;
-    let num_items = forecast.len().min(4) as i32;
+// 4時間分の予報を region 内に表示
+pub fn draw_forecast(fb: &mut FrameBuffer, region: &Rect, forecast: &[Hour], theme: &Theme) {
+    let (unit_width, unit_height) = forecast_unit_size(&theme.font);
+    let pixel_size = fit_pixel_size(unit_width, unit_height, region);
+    let item_width = ITEM_UNIT_WIDTH * pixel_size;
+    let num_items = forecast.len().min(MAX_ITEMS) as i32;
     let total_width = item_width * num_items;
-    let start_x = (800 - total_width) / 2;
-
-    for (i, hour) in forecast.iter().take(4).enumerate() {
-        // 各アイテムの中央X座標を計算
-        let center_x = start_x + (i as i32) * item_width + item_width / 2;
-        draw_forecast_item(fb, hour, center_x, start_y);
+    let start_x = region.centered_x(total_width);
+
+    for (i, hour) in forecast.iter().take(MAX_ITEMS).enumerate() {
+        let item = Rect::new(
+            start_x + (i as i32) * item_width,
+            region.y,
+            item_width,
+            region.height,
+        );
+        draw_forecast_item(fb, hour, &item, pixel_size, theme);
     }
 }
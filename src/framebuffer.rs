@@ -6,6 +6,13 @@ use embedded_graphics_core::{
     Pixel,
 };
 
+use crate::layout::Rect;
+
+// Rgb888 をバッファの形式 (0x00RRGGBB) に変換
+pub fn to_u32(color: Rgb888) -> u32 {
+    ((color.r() as u32) << 16) | ((color.g() as u32) << 8) | (color.b() as u32)
+}
+
 pub struct FrameBuffer {
     pub buffer: Vec<u32>,
     pub width: usize,
@@ -24,6 +31,26 @@ impl FrameBuffer {
     pub fn clear(&mut self, color: u32) {
         self.buffer.fill(color);
     }
+
+    // 矩形内の各色を背景色 bg に近づけて暗くする（brightness は 0.0〜1.0）
+    pub fn dim(&mut self, rect: &Rect, bg: u32, brightness: f32) {
+        let x0 = rect.x.clamp(0, self.width as i32) as usize;
+        let x1 = (rect.x + rect.width).clamp(0, self.width as i32) as usize;
+        let y0 = rect.y.clamp(0, self.height as i32) as usize;
+        let y1 = (rect.y + rect.height).clamp(0, self.height as i32) as usize;
+        let blend = |c: u32, shift: u32| {
+            let c = ((c >> shift) & 0xFF) as f32;
+            let b = ((bg >> shift) & 0xFF) as f32;
+            ((b + (c - b) * brightness) as u32) << shift
+        };
+
+        for y in y0..y1 {
+            for pixel in &mut self.buffer[y * self.width + x0..y * self.width + x1] {
+                let color = *pixel;
+                *pixel = blend(color, 16) | blend(color, 8) | blend(color, 0);
+            }
+        }
+    }
 }
 
 impl OriginDimensions for FrameBuffer {
@@ -47,8 +74,7 @@ impl DrawTarget for FrameBuffer {
                 && (coord.y as usize) < self.height
             {
                 let index = coord.y as usize * self.width + coord.x as usize;
-                self.buffer[index] =
-                    ((color.r() as u32) << 16) | ((color.g() as u32) << 8) | (color.b() as u32);
+                self.buffer[index] = to_u32(color);
             }
         }
         Ok(())
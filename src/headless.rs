@@ -0,0 +1,102 @@
+use chrono::{DateTime, Duration, Local};
+use image::{ImageResult, RgbImage};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::display::DisplayBackend;
+use crate::framebuffer::FrameBuffer;
+use crate::render::{draw_scene, Theme, WeatherView};
+
+// FrameBuffer (0x00RRGGBB) を PNG として保存
+pub fn save_png(fb: &FrameBuffer, path: &Path) -> ImageResult<()> {
+    let mut img = RgbImage::new(fb.width as u32, fb.height as u32);
+    for (pixel, &color) in img.pixels_mut().zip(fb.buffer.iter()) {
+        pixel.0 = [(color >> 16) as u8, (color >> 8) as u8, color as u8];
+    }
+    img.save(path)
+}
+
+// 書き出し先
+enum PngTarget {
+    // 毎回同じファイルに上書き
+    File(PathBuf),
+    // dir/frame_0000.png から連番で書き出し
+    Sequence { dir: PathBuf, next: usize },
+}
+
+// 表示の代わりに PNG へ書き出す出力先
+pub struct PngSink {
+    target: PngTarget,
+    width: usize,
+    height: usize,
+}
+
+impl PngSink {
+    pub fn file(path: &Path, width: usize, height: usize) -> Self {
+        Self {
+            target: PngTarget::File(path.to_path_buf()),
+            width,
+            height,
+        }
+    }
+
+    pub fn sequence(dir: &Path, width: usize, height: usize) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            target: PngTarget::Sequence {
+                dir: dir.to_path_buf(),
+                next: 0,
+            },
+            width,
+            height,
+        })
+    }
+}
+
+impl DisplayBackend for PngSink {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn present(&mut self, fb: &FrameBuffer) -> io::Result<()> {
+        let path = match &mut self.target {
+            PngTarget::File(path) => path.clone(),
+            PngTarget::Sequence { dir, next } => {
+                let path = dir.join(format!("frame_{:04}.png", next));
+                *next += 1;
+                path
+            }
+        };
+        save_png(fb, &path).map_err(io::Error::other)
+    }
+}
+
+// 指定時刻の1フレームを描画して出力
+pub fn render_frame(
+    sink: &mut dyn DisplayBackend,
+    at: &DateTime<Local>,
+    view: &WeatherView,
+    theme: &Theme,
+) -> io::Result<()> {
+    let (width, height) = sink.size();
+    let mut fb = FrameBuffer::new(width, height);
+    draw_scene(&mut fb, at, view, theme);
+    sink.present(&fb)
+}
+
+// start から interval ごとに count 枚のフレームを描画して出力
+pub fn render_sequence(
+    sink: &mut dyn DisplayBackend,
+    start: &DateTime<Local>,
+    count: usize,
+    interval: Duration,
+    view: &WeatherView,
+    theme: &Theme,
+) -> io::Result<()> {
+    let mut at = *start;
+    for _ in 0..count {
+        render_frame(sink, &at, view, theme)?;
+        at += interval;
+    }
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::framebuffer::FrameBuffer;
+use crate::weather::WeatherIconType;
+
+// アイコン画像の一辺の大きさ（拡大前）
+pub const ICON_SIZE: i32 = 16;
+
+// PNG アイコンデータ（コンパイル時に埋め込み）
+const ICON_SUN_PNG: &[u8] = include_bytes!("../assets/icons/sun.png");
+const ICON_MOON_PNG: &[u8] = include_bytes!("../assets/icons/moon.png");
+const ICON_CLOUD_PNG: &[u8] = include_bytes!("../assets/icons/cloud.png");
+const ICON_RAIN_PNG: &[u8] = include_bytes!("../assets/icons/rain.png");
+const ICON_HEAVY_RAIN_PNG: &[u8] = include_bytes!("../assets/icons/heavy_rain.png");
+const ICON_SNOW_PNG: &[u8] = include_bytes!("../assets/icons/snow.png");
+const ICON_THUNDER_PNG: &[u8] = include_bytes!("../assets/icons/thunder.png");
+
+fn icon_png(icon: WeatherIconType) -> &'static [u8] {
+    match icon {
+        WeatherIconType::Sun => ICON_SUN_PNG,
+        WeatherIconType::Moon => ICON_MOON_PNG,
+        WeatherIconType::Cloud => ICON_CLOUD_PNG,
+        WeatherIconType::Rain => ICON_RAIN_PNG,
+        WeatherIconType::HeavyRain => ICON_HEAVY_RAIN_PNG,
+        WeatherIconType::Snow => ICON_SNOW_PNG,
+        WeatherIconType::Thunder => ICON_THUNDER_PNG,
+    }
+}
+
+// 拡大済みのアイコン画像（None は透明ピクセル）
+pub struct IconBitmap {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Option<u32>>,
+}
+
+impl IconBitmap {
+    // PNG をデコードして scale 倍に拡大
+    fn decode(png_data: &[u8], scale: usize) -> Self {
+        let img = image::load_from_memory(png_data)
+            .expect("Failed to load PNG icon")
+            .to_rgba8();
+        let width = img.width() as usize * scale;
+        let height = img.height() as usize * scale;
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let [r, g, b, a] = img.get_pixel((x / scale) as u32, (y / scale) as u32).0;
+                // 透明度が128以上のピクセルのみ描画
+                pixels.push(if a > 128 {
+                    Some(((r as u32) << 16) | ((g as u32) << 8) | (b as u32))
+                } else {
+                    None
+                });
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    // FrameBuffer へ直接書き込み（画面外ははみ出し分を切り捨て）
+    fn blit(&self, fb: &mut FrameBuffer, x: i32, y: i32) {
+        for row in 0..self.height {
+            let dy = y + row as i32;
+            if dy < 0 || dy as usize >= fb.height {
+                continue;
+            }
+            let src = &self.pixels[row * self.width..(row + 1) * self.width];
+            let dst_row = dy as usize * fb.width;
+            for (col, pixel) in src.iter().enumerate() {
+                let dx = x + col as i32;
+                if dx < 0 || dx as usize >= fb.width {
+                    continue;
+                }
+                if let Some(color) = pixel {
+                    fb.buffer[dst_row + dx as usize] = *color;
+                }
+            }
+        }
+    }
+}
+
+// デコード済みアイコンのキャッシュ（アイコン種別と倍率ごと）
+#[derive(Default)]
+pub struct IconAtlas {
+    bitmaps: HashMap<(WeatherIconType, i32), IconBitmap>,
+}
+
+impl IconAtlas {
+    pub fn get(&mut self, icon: WeatherIconType, scale: i32) -> &IconBitmap {
+        self.bitmaps
+            .entry((icon, scale))
+            .or_insert_with(|| IconBitmap::decode(icon_png(icon), scale.max(1) as usize))
+    }
+
+    pub fn draw(
+        &mut self,
+        fb: &mut FrameBuffer,
+        icon: WeatherIconType,
+        x: i32,
+        y: i32,
+        scale: i32,
+    ) {
+        self.get(icon, scale).blit(fb, x, y);
+    }
+}
+
+thread_local! {
+    static ATLAS: RefCell<IconAtlas> = RefCell::new(IconAtlas::default());
+}
+
+pub fn draw_weather_icon(fb: &mut FrameBuffer, icon: WeatherIconType, x: i32, y: i32, scale: i32) {
+    ATLAS.with(|atlas| atlas.borrow_mut().draw(fb, icon, x, y, scale));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{
+        pixelcolor::Rgb888,
+        prelude::*,
+        primitives::{PrimitiveStyle, Rectangle},
+    };
+    use std::time::Instant;
+
+    const ALL_ICONS: [WeatherIconType; 7] = [
+        WeatherIconType::Sun,
+        WeatherIconType::Moon,
+        WeatherIconType::Cloud,
+        WeatherIconType::Rain,
+        WeatherIconType::HeavyRain,
+        WeatherIconType::Snow,
+        WeatherIconType::Thunder,
+    ];
+
+    // キャッシュ導入前の描画方法（毎回 PNG をデコード）
+    fn draw_png_icon_uncached(fb: &mut FrameBuffer, png_data: &[u8], x: i32, y: i32, scale: i32) {
+        use image::GenericImageView;
+
+        let img = image::load_from_memory(png_data).unwrap();
+        for (px, py, pixel) in img.pixels() {
+            let [r, g, b, a] = pixel.0;
+            if a > 128 {
+                Rectangle::new(
+                    Point::new(x + (px as i32) * scale, y + (py as i32) * scale),
+                    Size::new(scale as u32, scale as u32),
+                )
+                .into_styled(PrimitiveStyle::with_fill(Rgb888::new(r, g, b)))
+                .draw(fb)
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn atlas_matches_uncached_drawing() {
+        let mut atlas = IconAtlas::default();
+        for icon in ALL_ICONS {
+            for scale in [1, 2, 3] {
+                // 一部が画面外にはみ出す位置も確認
+                for (x, y) in [(4, 4), (-5, -7), (30, 20)] {
+                    let mut expected = FrameBuffer::new(48, 40);
+                    draw_png_icon_uncached(&mut expected, icon_png(icon), x, y, scale);
+                    let mut actual = FrameBuffer::new(48, 40);
+                    atlas.draw(&mut actual, icon, x, y, scale);
+                    assert!(expected.buffer == actual.buffer, "{:?} x{}", icon, scale);
+                }
+            }
+        }
+    }
+
+    // cargo test --release -- --ignored --nocapture bench_icon_drawing
+    #[test]
+    #[ignore]
+    fn bench_icon_drawing() {
+        const FRAMES: u32 = 300;
+        let mut fb = FrameBuffer::new(800, 480);
+
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            for (i, icon) in ALL_ICONS.iter().take(4).enumerate() {
+                draw_png_icon_uncached(&mut fb, icon_png(*icon), i as i32 * 180, 390, 2);
+            }
+        }
+        let uncached = start.elapsed();
+
+        let mut atlas = IconAtlas::default();
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            for (i, icon) in ALL_ICONS.iter().take(4).enumerate() {
+                atlas.draw(&mut fb, *icon, i as i32 * 180, 390, 2);
+            }
+        }
+        let cached = start.elapsed();
+
+        println!(
+            "{} frames x 4 icons: decode every frame {:?} ({:?}/frame), atlas {:?} ({:?}/frame)",
+            FRAMES,
+            uncached,
+            uncached / FRAMES,
+            cached,
+            cached / FRAMES,
+        );
+        assert!(cached < uncached);
+    }
+}
@@ -0,0 +1,86 @@
+// 画面サイズに応じた配置計算（800x480 を基準に比率で拡大縮小）
+const BASE_HEIGHT: i32 = 480;
+
+// 画面内の矩形領域
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // 幅 content_width の要素を中央寄せしたときの左端X座標
+    pub fn centered_x(&self, content_width: i32) -> i32 {
+        self.x + (self.width - content_width) / 2
+    }
+
+    // weights の比で上から順に縦に分割
+    pub fn split_rows(&self, weights: &[i32]) -> Vec<Rect> {
+        split(self.y, self.height, weights)
+            .into_iter()
+            .map(|(y, height)| Rect::new(self.x, y, self.width, height))
+            .collect()
+    }
+
+    // weights の比で左から順に横に分割
+    pub fn split_columns(&self, weights: &[i32]) -> Vec<Rect> {
+        split(self.x, self.width, weights)
+            .into_iter()
+            .map(|(x, width)| Rect::new(x, self.y, width, self.height))
+            .collect()
+    }
+}
+
+// 画面全体から左右に 5% ずつ余白を除いた、パネルを並べる領域
+pub fn screen_region(width: usize, height: usize) -> Rect {
+    let width = width as i32;
+    let margin_x = width / 20;
+    Rect::new(margin_x, 0, width - margin_x * 2, height as i32)
+}
+
+// 右上の状態表示の領域（縦位置は基準画面 (480px) での位置を比率で換算）
+pub fn status_region(width: usize, height: usize) -> Rect {
+    let screen = screen_region(width, height);
+    let height = height as i32;
+    Rect::new(
+        screen.x + screen.width * 3 / 4,
+        height * 10 / BASE_HEIGHT,
+        screen.width / 4,
+        height * 24 / BASE_HEIGHT,
+    )
+}
+
+// start から length の範囲を weights の比で分割した (開始位置, 長さ) の列
+fn split(start: i32, length: i32, weights: &[i32]) -> Vec<(i32, i32)> {
+    // 比の合計や length との積が i32 を超えないよう i64 で計算する
+    let total = weights.iter().map(|&w| i64::from(w)).sum::<i64>().max(1);
+    let offset = |sum: i64| (i64::from(length) * sum / total) as i32;
+    let mut sum = 0;
+    weights
+        .iter()
+        .map(|&weight| {
+            let begin = start + offset(sum);
+            sum += i64::from(weight);
+            (begin, start + offset(sum) - begin)
+        })
+        .collect()
+}
+
+// pixel_size=1 のとき unit_width x unit_height になる要素が
+// region に収まる最大の pixel_size（最低 1）
+pub fn fit_pixel_size(unit_width: i32, unit_height: i32, region: &Rect) -> i32 {
+    let by_width = region.width / unit_width.max(1);
+    let by_height = region.height / unit_height.max(1);
+    by_width.min(by_height).max(1)
+}
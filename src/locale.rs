@@ -0,0 +1,115 @@
+use chrono::Weekday;
+use serde::Deserialize;
+
+use crate::weather::WeatherIconType;
+
+// 画面の表示言語（設定ファイルの locale）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    // "2026-01-12 MON"
+    #[default]
+    En,
+    // "1月12日(月)"
+    Ja,
+}
+
+// 今日の概要の見出し
+pub struct SummaryLabels {
+    pub tomorrow: &'static str,
+    pub high: &'static str,
+    pub low: &'static str,
+    pub sunrise: &'static str,
+    pub sunset: &'static str,
+}
+
+impl Locale {
+    pub fn weekday(self, weekday: Weekday) -> &'static str {
+        let index = weekday.num_days_from_monday() as usize;
+        match self {
+            Locale::En => ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"][index],
+            Locale::Ja => ["月", "火", "水", "木", "金", "土", "日"][index],
+        }
+    }
+
+    // 天気の名前
+    pub fn condition(self, icon: WeatherIconType) -> &'static str {
+        match (self, icon) {
+            (Locale::En, WeatherIconType::Sun) => "SUNNY",
+            (Locale::En, WeatherIconType::Moon) => "CLEAR",
+            (Locale::En, WeatherIconType::Cloud) => "CLOUDY",
+            (Locale::En, WeatherIconType::Rain) => "RAIN",
+            (Locale::En, WeatherIconType::HeavyRain) => "HEAVY RAIN",
+            (Locale::En, WeatherIconType::Snow) => "SNOW",
+            (Locale::En, WeatherIconType::Thunder) => "STORM",
+            (Locale::Ja, WeatherIconType::Sun | WeatherIconType::Moon) => "晴れ",
+            (Locale::Ja, WeatherIconType::Cloud) => "曇り",
+            (Locale::Ja, WeatherIconType::Rain) => "雨",
+            (Locale::Ja, WeatherIconType::HeavyRain) => "大雨",
+            (Locale::Ja, WeatherIconType::Snow) => "雪",
+            (Locale::Ja, WeatherIconType::Thunder) => "雷",
+        }
+    }
+
+    pub fn summary_labels(self) -> SummaryLabels {
+        match self {
+            Locale::En => SummaryLabels {
+                tomorrow: "TMRW",
+                high: "H",
+                low: "L",
+                sunrise: "RISE",
+                sunset: "SET",
+            },
+            Locale::Ja => SummaryLabels {
+                tomorrow: "明日",
+                high: "最高",
+                low: "最低",
+                sunrise: "日の出",
+                sunset: "日の入り",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::Font;
+
+    // 表示する文字がすべてフォントにあること
+    #[test]
+    fn all_labels_have_glyphs() {
+        let icons = [
+            WeatherIconType::Sun,
+            WeatherIconType::Moon,
+            WeatherIconType::Cloud,
+            WeatherIconType::Rain,
+            WeatherIconType::HeavyRain,
+            WeatherIconType::Snow,
+            WeatherIconType::Thunder,
+        ];
+        for locale in [Locale::En, Locale::Ja] {
+            let labels = locale.summary_labels();
+            let mut texts = vec![
+                "月日()",
+                labels.tomorrow,
+                labels.high,
+                labels.low,
+                labels.sunrise,
+                labels.sunset,
+            ];
+            texts.extend((0..7).map(|i| locale.weekday(Weekday::try_from(i as u8).unwrap())));
+            texts.extend(icons.map(|icon| locale.condition(icon)));
+            for text in texts {
+                for ch in text.chars() {
+                    assert!(
+                        Font::builtin().glyph(ch).is_some(),
+                        "{:?} の {:?}",
+                        text,
+                        ch
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,82 +1,376 @@
+mod bdf;
+mod cache;
+mod cli;
 mod clock;
+mod config;
+mod current;
+mod day_summary;
+mod display;
+#[cfg(target_os = "linux")]
+mod fbdev;
 mod font;
 mod forecast;
 mod framebuffer;
+mod headless;
+mod icons;
+mod layout;
+mod locale;
+mod refresh;
+mod render;
+#[cfg(test)]
+mod snapshot;
+mod status;
+mod temperature;
+mod text;
+mod time_source;
 mod weather;
+mod widget;
 
-use chrono::{Datelike, Local};
-use minifb::{Key, Window, WindowOptions};
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use clock::{draw_date, draw_time};
-use forecast::draw_forecast;
+use cli::{Cli, Command, FetchArgs, RenderArgs, RunArgs};
+use config::{Config, ConfigError, ConfigWatcher};
+use display::{DisplayBackend, InputEvent, MinifbDisplay};
 use framebuffer::FrameBuffer;
-use weather::{get_weather, Weather};
+use headless::PngSink;
+use refresh::WeatherState;
+use render::{draw_scene, SceneState, Theme, WeatherView};
+use time_source::{Clock, FixedClock, SimulatedClock, SystemClock};
+use weather::{get_weather, Weather, WeatherProvider};
 
-const WINDOW_WIDTH: usize = 800;
-const WINDOW_HEIGHT: usize = 480;
-const BG_COLOR: u32 = 0x001020;
+// 表示先を開く（--fbdev があればフレームバッファデバイス、なければウィンドウ）
+fn open_display(args: &RunArgs, size: (usize, usize), fps: usize) -> Box<dyn DisplayBackend> {
+    #[cfg(target_os = "linux")]
+    if let Some(path) = &args.fbdev {
+        let result = match args.fbdev_format {
+            Some(format) => fbdev::FbDev::open_with_format(path, format),
+            None => fbdev::FbDev::open(path),
+        };
+        return match result {
+            Ok(dev) => Box::new(dev),
+            Err(e) => {
+                eprintln!("{} を開けません: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        };
+    }
+
+    let (width, height) = args.size.unwrap_or(size);
+    Box::new(
+        MinifbDisplay::new("Home Clock", width, height, fps)
+            .expect("ウィンドウの作成に失敗しました"),
+    )
+}
+
+// --at, --speed があれば指定時刻から指定倍速で進む時計、なければシステムの時計
+fn run_clock(args: &RunArgs) -> Arc<dyn Clock> {
+    if args.at.is_none() && args.speed.is_none() {
+        return Arc::new(SystemClock);
+    }
+    let start = args.at.map_or_else(Local::now, |at| {
+        FixedClock::at(at)
+            .expect("指定時刻をローカル時刻に変換できません")
+            .now()
+    });
+    Arc::new(SimulatedClock::new(start, args.speed.unwrap_or(1.0)))
+}
+
+// 設定から見た目を作る（フォントを読み込めなければ終了）
+fn load_theme(config: &Config) -> Theme {
+    config.theme().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    })
+}
+
+// ウィンドウを開かずに PNG へ描画
+fn render(rt: &tokio::runtime::Runtime, config: &Config, args: RenderArgs) {
+    let theme = load_theme(config);
+    let clock: Box<dyn Clock> = match args.at {
+        Some(at) => Box::new(FixedClock::at(at).expect("指定時刻をローカル時刻に変換できません")),
+        None => Box::new(SystemClock),
+    };
+    let start = clock.now();
+
+    // 天気は --weather のファイル、なければ取得できた場合のみ描画
+    // （動作中の時計のオフライン用キャッシュを上書きしないよう、キャッシュは更新しない）
+    let result = match &args.weather {
+        Some(path) => Ok(load_weather_file(path)),
+        None => {
+            weather::provider(&config.weather).and_then(|provider| rt.block_on(provider.fetch()))
+        }
+    };
+    let (weather, weather_error) = match result {
+        Ok(weather) => (Some(weather), None),
+        Err(e) => {
+            eprintln!("天気の取得に失敗しました: {}", e);
+            (None, Some(e))
+        }
+    };
+    let view = WeatherView {
+        weather: weather.as_ref(),
+        error: weather_error.as_ref(),
+        stale_age: None,
+        config_error: None,
+    };
+
+    // 1枚ならファイル、連番なら出力ディレクトリ
+    let out = args.out.unwrap_or_else(|| {
+        PathBuf::from(if args.frames > 1 {
+            "frames"
+        } else {
+            "frame.png"
+        })
+    });
+    let (width, height) = args
+        .size
+        .unwrap_or((config.display.width, config.display.height));
+    let result = if args.frames > 1 {
+        PngSink::sequence(&out, width, height).and_then(|mut sink| {
+            headless::render_sequence(
+                &mut sink,
+                &start,
+                args.frames,
+                chrono::Duration::seconds(args.interval),
+                &view,
+                &theme,
+            )
+        })
+    } else {
+        let mut sink = PngSink::file(&out, width, height);
+        headless::render_frame(&mut sink, &start, &view, &theme)
+    };
+    result.expect("PNG の書き出しに失敗しました");
+}
+
+// fetch --dump で書き出した JSON を読み込む
+fn load_weather_file(path: &Path) -> Weather {
+    let weather = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()));
+    weather.unwrap_or_else(|message| {
+        eprintln!("{} を読み込めません: {}", path.display(), message);
+        std::process::exit(2);
+    })
+}
+
+// 天気を取得して表示する（キャッシュは更新しない）
+fn fetch(rt: &tokio::runtime::Runtime, config: &Config, args: FetchArgs) {
+    let provider = weather::provider(&config.weather).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    });
+    let weather = rt.block_on(provider.fetch()).unwrap_or_else(|e| {
+        eprintln!("天気の取得に失敗しました: {}", e);
+        std::process::exit(1);
+    });
+
+    if args.dump {
+        let json = serde_json::to_string_pretty(&weather).expect("JSON に変換できません");
+        println!("{}", json);
+        return;
+    }
+    println!(
+        "現在: {:.1}° {:?}",
+        weather.current.temp_c, weather.current.icon
+    );
+    if let (Some(first), Some(last)) = (weather.hours.first(), weather.hours.last()) {
+        println!(
+            "時間ごとの予報: {} 件 ({} 〜 {})",
+            weather.hours.len(),
+            first.time.format("%m-%d %H:%M"),
+            last.time.format("%m-%d %H:%M")
+        );
+    }
+    for day in &weather.days {
+        let temperature = |t: Option<f64>| t.map_or("--".to_string(), |t| format!("{:.1}°", t));
+        println!(
+            "{}: 最高 {} 最低 {}",
+            day.date,
+            temperature(day.maxtemp_c),
+            temperature(day.mintemp_c)
+        );
+    }
+}
+
+// 設定ファイルの読み込み結果と天気の取得元を確認する
+fn check_config(path: Option<&Path>, config: &Config) {
+    match Config::source_path(path) {
+        Some(path) => println!("設定ファイル: {}", path.display()),
+        None => println!("設定ファイル: なし（既定値を使用）"),
+    }
+    println!(
+        "画面: {}x{} ({} fps)",
+        config.display.width, config.display.height, config.display.fps
+    );
+    println!(
+        "天気の取得: {} 分ごと（{} 分で古いデータとして表示）",
+        config.refresh.interval_minutes, config.refresh.stale_after_minutes
+    );
+    match config.theme() {
+        Ok(theme) => match &config.font {
+            Some(path) => println!(
+                "フォント: {}（{} 文字、高さ {}）",
+                path.display(),
+                theme.font.glyph_count(),
+                theme.font.height
+            ),
+            None => println!("フォント: 内蔵"),
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+    if let Err(e) = weather::provider(&config.weather) {
+        eprintln!("天気の取得元の設定が不正です: {}", e);
+        std::process::exit(2);
+    }
+    println!("OK");
+}
 
 fn main() {
+    let cli = Cli::parse_args();
+    let config_path = cli.config.clone();
+    let config = Config::load(config_path.as_deref()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    });
     let rt = tokio::runtime::Runtime::new().unwrap();
 
-    let mut fb = FrameBuffer::new(WINDOW_WIDTH, WINDOW_HEIGHT);
-    let mut window = Window::new(
-        "Home Clock",
-        WINDOW_WIDTH,
-        WINDOW_HEIGHT,
-        WindowOptions::default(),
-    )
-    .expect("ウィンドウの作成に失敗しました");
-
-    window.set_target_fps(30);
-
-    // 天気データを保持
-    let weather_data: Arc<Mutex<Option<Weather>>> = Arc::new(Mutex::new(None));
-    let mut last_weather_fetch = Instant::now();
-    let mut first_run = true;
-
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        // 起動時と10分ごとに天気を取得
-        if first_run || last_weather_fetch.elapsed() > Duration::from_secs(600) {
-            first_run = false;
-            let weather_clone = Arc::clone(&weather_data);
-            rt.spawn(async move {
-                if let Ok(weather) = get_weather().await {
-                    let mut data = weather_clone.lock().unwrap();
-                    *data = Some(weather);
-                }
-            });
-            last_weather_fetch = Instant::now();
+    match cli.into_command() {
+        Command::Run(args) => {
+            let watcher = ConfigWatcher::new(config_path.as_deref(), Instant::now());
+            let theme = load_theme(&config);
+            let clock = run_clock(&args);
+            let size = (config.display.width, config.display.height);
+            let mut display = open_display(&args, size, config.display.fps);
+            run_loop(&rt, display.as_mut(), clock, config, theme, watcher);
         }
+        Command::Render(args) => render(&rt, &config, args),
+        Command::Fetch(args) => fetch(&rt, &config, args),
+        Command::CheckConfig => check_config(config_path.as_deref(), &config),
+    }
+}
 
-        fb.clear(BG_COLOR);
+// 入力イベントを確認する最大間隔
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
-        let now = Local::now();
-        let hour = now.format("%H").to_string().parse::<u8>().unwrap();
-        let minute = now.format("%M").to_string().parse::<u8>().unwrap();
-        let year = now.year() as u16;
-        let month = now.month() as u8;
-        let day = now.day() as u8;
-        let weekday = now.weekday();
+// 取得元を作る。設定に誤りがあれば、その理由を表示したまま取得しない
+fn start_provider(
+    config: &Config,
+    state: &mut WeatherState,
+    now: &DateTime<Local>,
+) -> Option<Arc<dyn WeatherProvider>> {
+    weather::provider(&config.weather)
+        .map(Arc::from)
+        .map_err(|e| state.apply(Err(e), now))
+        .ok()
+}
 
-        let blink = now.timestamp_subsec_millis() < 500;
+// 描画ループ本体。出力先が閉じるか終了要求があるまで、表示内容が変わったときだけ描画する
+// 設定ファイルが変更されれば読み直して反映する（画面サイズと fps は再起動が必要）
+fn run_loop(
+    rt: &tokio::runtime::Runtime,
+    display: &mut dyn DisplayBackend,
+    clock: Arc<dyn Clock>,
+    mut config: Config,
+    mut theme: Theme,
+    mut watcher: ConfigWatcher,
+) {
+    let (width, height) = display.size();
+    let mut fb = FrameBuffer::new(width, height);
+    let mut refresh = config.refresh();
 
-        draw_date(&mut fb, year, month, day, weekday);
-        draw_time(&mut fb, hour, minute, blink);
+    // 天気データを保持（前回保存したデータがあれば取得完了まではそれを表示）
+    let mut state = WeatherState::new(&refresh);
+    if let Some(cached) = cache::restore() {
+        state.restore(cached);
+    }
+    let mut provider = start_provider(&config, &mut state, &clock.now());
+    let weather_data = Arc::new(Mutex::new(state));
+    let mut last_scene: Option<SceneState> = None;
+    // 時間の経過で次に表示が変わる時刻
+    let mut redraw_at: Option<DateTime<Local>> = None;
+    let mut config_error: Option<ConfigError> = None;
+    let mut config_generation = 0;
+
+    while display.is_open() {
+        if display.poll_events().contains(&InputEvent::Quit) {
+            break;
+        }
 
-        // 予報を描画
-        if let Ok(data) = weather_data.lock() {
-            if let Some(ref weather) = *data {
-                draw_forecast(&mut fb, &weather.forecast);
+        // 読み直しに失敗した場合は、直前の設定のまま理由を表示する
+        let reloaded = watcher
+            .poll(Instant::now())
+            .map(|result| result.and_then(|c| c.theme().map(|theme| (c, theme))));
+        if reloaded.is_some() {
+            config_generation += 1;
+        }
+        match reloaded {
+            Some(Ok((new_config, new_theme))) => {
+                theme = new_theme;
+                refresh = new_config.refresh();
+                let mut data = weather_data.lock().unwrap();
+                if new_config.weather != config.weather {
+                    data.reset(&refresh);
+                    provider = start_provider(&new_config, &mut data, &clock.now());
+                } else {
+                    data.scheduler.set_interval(refresh.interval);
+                }
+                config = new_config;
+                config_error = None;
+            }
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                config_error = Some(e);
             }
+            None => {}
         }
 
-        window
-            .update_with_buffer(&fb.buffer, fb.width, fb.height)
-            .expect("バッファの更新に失敗しました");
+        let now = clock.now();
+        if let Ok(mut data) = weather_data.lock() {
+            // 起動時と取得間隔ごと、失敗時は間隔を広げながら再取得
+            if let Some(provider) = &provider {
+                if data.scheduler.is_due(Instant::now()) {
+                    data.scheduler.start();
+                    let provider = Arc::clone(provider);
+                    let source = data.source;
+                    let clock = Arc::clone(&clock);
+                    let weather_clone = Arc::clone(&weather_data);
+                    rt.spawn(async move {
+                        let result = get_weather(provider.as_ref()).await;
+                        let mut data = weather_clone.lock().unwrap();
+                        if data.source == source {
+                            data.apply(result, &clock.now());
+                        }
+                    });
+                }
+            }
+
+            let stale_age = data.stale_age(&now, refresh.stale_after);
+            let scene = SceneState::new(data.generation, stale_age.is_some(), config_generation);
+            let due = redraw_at.is_some_and(|at| now >= at);
+            if last_scene != Some(scene) || due {
+                let view = WeatherView {
+                    weather: data.weather.as_ref(),
+                    error: data.last_error.as_ref(),
+                    stale_age,
+                    config_error: config_error.as_ref(),
+                };
+                redraw_at = draw_scene(&mut fb, &now, &view, &theme);
+                display.present(&fb).expect("バッファの更新に失敗しました");
+                last_scene = Some(scene);
+            }
+        }
 
-        std::thread::sleep(Duration::from_millis(16));
+        // 次に表示が変わるまで待つ（入力確認のため最大 INPUT_POLL_INTERVAL）
+        let wait = redraw_at
+            .map(|at| (at - clock.now()).to_std().unwrap_or_default())
+            .unwrap_or(INPUT_POLL_INTERVAL);
+        std::thread::sleep(wait.min(INPUT_POLL_INTERVAL));
     }
 }
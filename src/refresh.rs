@@ -0,0 +1,185 @@
+use chrono::{DateTime, Local};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use crate::cache::CachedWeather;
+use crate::weather::{Weather, WeatherError};
+
+// 失敗時の最初の再試行までの待ち時間
+const RETRY_BASE: Duration = Duration::from_secs(15);
+
+// 天気の再取得に関する設定
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RefreshConfig {
+    // 成功後、次に取得するまでの間隔
+    pub interval: Duration,
+    // 取得時刻からこの時間が過ぎたデータは古いものとして表示
+    pub stale_after: Duration,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(600),
+            stale_after: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+// 0.5〜1.0 の乱数（再試行の時刻を分散させる）
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    0.5 + (hasher.finish() % 1000) as f64 / 2000.0
+}
+
+// 次に天気を取得する時刻を決める
+#[derive(Debug)]
+pub struct RefreshScheduler {
+    interval: Duration,
+    // 連続して失敗した回数
+    failures: u32,
+    next_fetch: Instant,
+    in_flight: bool,
+}
+
+impl RefreshScheduler {
+    // 起動直後にすぐ取得する
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            failures: 0,
+            next_fetch: now,
+            in_flight: false,
+        }
+    }
+
+    // 次回の取得から新しい間隔を使う
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        !self.in_flight && now >= self.next_fetch
+    }
+
+    pub fn start(&mut self) {
+        self.in_flight = true;
+    }
+
+    pub fn record_success(&mut self, now: Instant) {
+        self.in_flight = false;
+        self.failures = 0;
+        self.next_fetch = now + self.interval;
+    }
+
+    // 失敗するたびに待ち時間を倍にする（上限は通常の取得間隔）
+    pub fn record_failure(&mut self, now: Instant) {
+        self.in_flight = false;
+        self.failures += 1;
+        self.next_fetch = now + self.backoff().mul_f64(jitter());
+    }
+
+    fn backoff(&self) -> Duration {
+        let exponent = self.failures.saturating_sub(1).min(16);
+        RETRY_BASE.saturating_mul(1 << exponent).min(self.interval)
+    }
+}
+
+// 取得済みの天気データと直近の取得エラー、その更新回数
+#[derive(Debug)]
+pub struct WeatherState {
+    pub weather: Option<Weather>,
+    // weather を取得した時刻
+    pub fetched_at: Option<DateTime<Local>>,
+    pub last_error: Option<WeatherError>,
+    pub generation: u64,
+    // 取得元を切り替えるたびに増える番号（切り替え前に始めた取得の結果は捨てる）
+    pub source: u64,
+    pub scheduler: RefreshScheduler,
+}
+
+impl WeatherState {
+    pub fn new(config: &RefreshConfig) -> Self {
+        Self {
+            weather: None,
+            fetched_at: None,
+            last_error: None,
+            generation: 0,
+            source: 0,
+            scheduler: RefreshScheduler::new(config.interval, Instant::now()),
+        }
+    }
+
+    // 取得元が変わったので、これまでのデータを捨ててすぐに取得し直す
+    pub fn reset(&mut self, config: &RefreshConfig) {
+        self.weather = None;
+        self.fetched_at = None;
+        self.last_error = None;
+        self.generation += 1;
+        self.source += 1;
+        self.scheduler = RefreshScheduler::new(config.interval, Instant::now());
+    }
+
+    // 保存済みの天気データを取得時刻とともに復元
+    pub fn restore(&mut self, cached: CachedWeather) {
+        self.fetched_at = cached.fetched_at();
+        self.weather = Some(cached.weather);
+        self.generation += 1;
+    }
+
+    // 取得結果を反映して次回の取得時刻を決める（now は表示上の現在時刻）
+    pub fn apply(&mut self, result: Result<Weather, WeatherError>, now: &DateTime<Local>) {
+        match result {
+            Ok(weather) => {
+                self.weather = Some(weather);
+                self.fetched_at = Some(*now);
+                self.last_error = None;
+                self.scheduler.record_success(Instant::now());
+            }
+            Err(e) => {
+                eprintln!("天気の取得に失敗しました: {}", e);
+                self.last_error = Some(e);
+                self.scheduler.record_failure(Instant::now());
+            }
+        }
+        self.generation += 1;
+    }
+
+    // データの経過時間が stale_after を超えていれば、その経過時間
+    pub fn stale_age(&self, now: &DateTime<Local>, stale_after: Duration) -> Option<Duration> {
+        let age = (*now - self.fetched_at?).to_std().ok()?;
+        (age > stale_after).then_some(age)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_is_capped_by_interval() {
+        let now = Instant::now();
+        let mut scheduler = RefreshScheduler::new(Duration::from_secs(600), now);
+
+        let mut waits = Vec::new();
+        for _ in 0..8 {
+            scheduler.start();
+            scheduler.record_failure(now);
+            assert!(!scheduler.is_due(now));
+            waits.push(scheduler.backoff());
+        }
+        let secs: Vec<u64> = waits.iter().map(Duration::as_secs).collect();
+        assert_eq!(secs, vec![15, 30, 60, 120, 240, 480, 600, 600]);
+
+        // 実際の待ち時間は backoff の半分〜等倍
+        let wait = scheduler.next_fetch - now;
+        assert!(wait >= Duration::from_secs(300) && wait <= Duration::from_secs(600));
+
+        scheduler.start();
+        scheduler.record_success(now);
+        assert_eq!(scheduler.failures, 0);
+        assert!(scheduler.is_due(now + Duration::from_secs(600)));
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Local};
+use embedded_graphics::pixelcolor::Rgb888;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::clock::TimeFormat;
+use crate::config::{deserialize_color, ConfigError};
+use crate::font::Font;
+use crate::framebuffer::{to_u32, FrameBuffer};
+use crate::layout::{screen_region, status_region};
+use crate::locale::Locale;
+use crate::status::draw_status;
+use crate::temperature::TemperatureScale;
+use crate::weather::{Weather, WeatherError};
+use crate::widget::{Context, Screen};
+
+// 描画する天気の情報
+#[derive(Clone, Copy, Default)]
+pub struct WeatherView<'a> {
+    pub weather: Option<&'a Weather>,
+    // 直近の取得エラー
+    pub error: Option<&'a WeatherError>,
+    // データが古い場合、取得からの経過時間
+    pub stale_age: Option<Duration>,
+    // 設定ファイルの読み直しに失敗していれば、その理由
+    pub config_error: Option<&'a ConfigError>,
+}
+
+// 経過時間を "STALE 45M" / "STALE 3H" の形式で表す
+fn stale_label(age: Duration) -> String {
+    let minutes = age.as_secs() / 60;
+    if minutes < 60 {
+        format!("STALE {}M", minutes)
+    } else {
+        format!("STALE {}H", minutes / 60)
+    }
+}
+
+// 表示色（設定ファイルの [colors]、"#rrggbb" 形式）
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Colors {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub background: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub date: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub time: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub current: Rgb888,
+    // 今日の概要の見出し ("H", "RISE" など) と値
+    #[serde(deserialize_with = "deserialize_color")]
+    pub label: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub value: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub forecast_time: Rgb888,
+    // 降水確率（50% 以上は rain_likely）
+    #[serde(deserialize_with = "deserialize_color")]
+    pub rain: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub rain_likely: Rgb888,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub status: Rgb888,
+    pub temperature_scale: TemperatureScale,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Self {
+            background: Rgb888::new(0x00, 0x10, 0x20),
+            date: Rgb888::new(180, 180, 180),
+            time: Rgb888::new(255, 255, 255),
+            current: Rgb888::new(230, 230, 230),
+            label: Rgb888::new(120, 120, 120),
+            value: Rgb888::new(180, 180, 180),
+            forecast_time: Rgb888::new(150, 150, 150),
+            rain: Rgb888::new(120, 120, 120),
+            rain_likely: Rgb888::new(100, 150, 255),
+            status: Rgb888::new(255, 120, 80),
+            temperature_scale: TemperatureScale::default(),
+        }
+    }
+}
+
+// 表示する項目（設定ファイルの [layout]）。[screen] に並べたパネルのうち false のものは空けておく
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sections {
+    pub date: bool,
+    // 最高・最低気温と日の出・日の入り
+    pub summary: bool,
+    pub current: bool,
+    pub forecast: bool,
+    // false ならコロンを点滅させない
+    pub blink_colon: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self {
+            date: true,
+            summary: true,
+            current: true,
+            forecast: true,
+            blink_colon: true,
+        }
+    }
+}
+
+// 表示色などの見た目の設定
+#[derive(Clone, Debug, Default)]
+pub struct Theme {
+    pub colors: Colors,
+    pub time_format: TimeFormat,
+    pub locale: Locale,
+    // 時刻の大きな数字以外の文字（既定は内蔵フォント）
+    pub font: Font,
+    // 設定の [screen] と [layout] から組み立てたパネル
+    pub screen: Screen,
+}
+
+// 画面の見た目を決める時刻以外の状態。変化したときと、パネルが指定した時刻に再描画する
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SceneState {
+    // 新しい天気データを受け取るたびに増える番号
+    weather_generation: u64,
+    stale: bool,
+    // 設定ファイルを読み込むたびに増える番号
+    config_generation: u64,
+}
+
+impl SceneState {
+    pub fn new(weather_generation: u64, stale: bool, config_generation: u64) -> Self {
+        Self {
+            weather_generation,
+            stale,
+            config_generation,
+        }
+    }
+}
+
+// 指定時刻の画面全体を描画し（ウィンドウ・PNG出力で共通）、時間の経過で次に表示が変わる時刻を返す
+pub fn draw_scene(
+    fb: &mut FrameBuffer,
+    now: &DateTime<Local>,
+    view: &WeatherView,
+    theme: &Theme,
+) -> Option<DateTime<Local>> {
+    let colors = &theme.colors;
+    fb.clear(to_u32(colors.background));
+
+    let ctx = Context { now, view, theme };
+    let screen = theme.screen.widget();
+    screen.draw(fb, &screen_region(fb.width, fb.height), &ctx);
+
+    // 設定ファイルの誤り、直近の天気取得に失敗した理由、データの古さの順に優先して表示
+    let status = match (view.config_error, view.error, view.stale_age) {
+        (Some(error), _, _) => Some(error.short_label().to_string()),
+        (None, Some(error), _) => Some(error.short_label()),
+        (None, None, Some(age)) => Some(stale_label(age)),
+        (None, None, None) => None,
+    };
+    if let Some(label) = status {
+        let region = status_region(fb.width, fb.height);
+        draw_status(fb, &region, &theme.font, &label, colors.status);
+    }
+    screen.next_redraw(&ctx)
+}
@@ -0,0 +1,328 @@
+// 描画結果を保存済みの基準画像 (src/snapshots/*.png) と比較するテスト
+// 意図して見た目を変えたときは UPDATE_SNAPSHOTS=1 cargo test で基準画像を更新する
+use image::{Rgb, RgbImage};
+use std::path::PathBuf;
+
+use crate::framebuffer::FrameBuffer;
+use crate::headless::save_png;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/snapshots")
+        .join(format!("{}.png", name))
+}
+
+// 不一致のときに実際の画像と差分画像を書き出す場所
+fn failure_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/snapshot-diff")
+}
+
+fn to_rgb(color: u32) -> Rgb<u8> {
+    Rgb([(color >> 16) as u8, (color >> 8) as u8, color as u8])
+}
+
+// 差分画像: 一致した画素は基準画像を暗くして、異なる画素は赤で示す
+fn diff_image(expected: &RgbImage, fb: &FrameBuffer) -> (RgbImage, usize) {
+    let mut diff = RgbImage::new(fb.width as u32, fb.height as u32);
+    let mut count = 0;
+    for (x, y, pixel) in diff.enumerate_pixels_mut() {
+        let actual = to_rgb(fb.buffer[y as usize * fb.width + x as usize]);
+        let expected = *expected.get_pixel(x, y);
+        *pixel = if actual == expected {
+            Rgb(expected.0.map(|c| c / 4))
+        } else {
+            count += 1;
+            Rgb([255, 0, 0])
+        };
+    }
+    (diff, count)
+}
+
+pub fn assert_snapshot(name: &str, fb: &FrameBuffer) {
+    let path = snapshot_path(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        save_png(fb, &path).expect("基準画像を保存できません");
+        return;
+    }
+
+    let expected = match image::open(&path) {
+        Ok(image) => image.to_rgb8(),
+        Err(e) => panic!(
+            "基準画像 {} を読み込めません: {} (UPDATE_SNAPSHOTS=1 cargo test で作成)",
+            path.display(),
+            e
+        ),
+    };
+    let dir = failure_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let actual_path = dir.join(format!("{}.actual.png", name));
+
+    let size = (fb.width as u32, fb.height as u32);
+    if expected.dimensions() != size {
+        save_png(fb, &actual_path).unwrap();
+        panic!(
+            "{}: 画像サイズが異なります（基準 {:?}、実際 {:?}）。実際の画像: {}",
+            name,
+            expected.dimensions(),
+            size,
+            actual_path.display()
+        );
+    }
+
+    let (diff, count) = diff_image(&expected, fb);
+    if count == 0 {
+        return;
+    }
+    save_png(fb, &actual_path).unwrap();
+    let diff_path = dir.join(format!("{}.diff.png", name));
+    diff.save(&diff_path).unwrap();
+    panic!(
+        "{}: {} 画素が基準画像と異なります。実際の画像: {}、差分: {}",
+        name,
+        count,
+        actual_path.display(),
+        diff_path.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeDelta};
+
+    use crate::clock::{draw_date, draw_time, HourFormat, Meridiem, TimeFormat};
+    use crate::config::Config;
+    use crate::font::Font;
+    use crate::forecast::draw_forecast;
+    use crate::framebuffer::to_u32;
+    use crate::icons::{draw_weather_icon, ICON_SIZE};
+    use crate::layout::Rect;
+    use crate::locale::Locale;
+    use crate::render::{draw_scene, Colors, Theme, WeatherView};
+    use crate::time_source::{Clock, FixedClock};
+    use crate::weather::{Current, DaySummary, Hour, Weather, WeatherIconType};
+
+    fn frame(width: usize, height: usize) -> FrameBuffer {
+        let mut fb = FrameBuffer::new(width, height);
+        fb.clear(to_u32(Colors::default().background));
+        fb
+    }
+
+    fn clock() -> FixedClock {
+        let at = NaiveDate::from_ymd_opt(2026, 1, 12)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        FixedClock::at(at).unwrap()
+    }
+
+    // 9:30 以降の予報（天気・気温・降水確率がそれぞれ異なる）
+    fn weather() -> Weather {
+        let now = clock().now();
+        let icons = [
+            WeatherIconType::Sun,
+            WeatherIconType::Cloud,
+            WeatherIconType::Rain,
+            WeatherIconType::Snow,
+        ];
+        let hours = (1..=4)
+            .map(|i| {
+                let time = now + TimeDelta::minutes(30 + 60 * (i - 1));
+                Hour {
+                    time: time.naive_local(),
+                    time_epoch: time.timestamp(),
+                    temp_c: Some(-4.0 + 9.0 * i as f64),
+                    chance_of_rain: (i * 30).min(100) as u8,
+                    icon: icons[i as usize - 1],
+                }
+            })
+            .collect();
+        Weather {
+            current: Current {
+                temp_c: 5.2,
+                icon: WeatherIconType::Cloud,
+            },
+            hours,
+            days: vec![DaySummary {
+                date: now.date_naive(),
+                maxtemp_c: Some(9.4),
+                mintemp_c: Some(0.8),
+                sunrise: "06:51:00".parse().ok(),
+                sunset: "16:49:00".parse().ok(),
+            }],
+        }
+    }
+
+    #[test]
+    fn date() {
+        let colors = Colors::default();
+        for (name, locale) in [("date", Locale::En), ("date_ja", Locale::Ja)] {
+            let mut fb = frame(400, 40);
+            draw_date(
+                &mut fb,
+                &Rect::new(0, 4, 400, 32),
+                clock().now().date_naive(),
+                Font::builtin(),
+                locale,
+                colors.date,
+            );
+            assert_snapshot(name, &fb);
+        }
+    }
+
+    #[test]
+    fn font() {
+        let lines = [
+            " !\"#$%&'()*+,-./0123456789:;<=>?",
+            "@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_",
+            "`abcdefghijklmnopqrstuvwxyz{|}~",
+            "°←→↑↓ 午前午後時 月火水木金土日",
+            "晴曇雨大雪雷 明最高低出入 のりれ",
+        ];
+        let mut fb = frame(600, 4 + 28 * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let y = 4 + 28 * row as i32;
+            Font::builtin().draw_text(&mut fb, line, 4, y, 2, Colors::default().time);
+        }
+        assert_snapshot("font", &fb);
+    }
+
+    #[test]
+    fn time() {
+        let colors = Colors::default();
+        for (name, blink) in [("time", true), ("time_colon_hidden", false)] {
+            let mut fb = frame(400, 120);
+            draw_time(
+                &mut fb,
+                &Rect::new(0, 10, 400, 100),
+                9,
+                5,
+                blink,
+                TimeFormat::default(),
+                colors.time,
+            );
+            assert_snapshot(name, &fb);
+        }
+    }
+
+    #[test]
+    fn time_12_hour() {
+        let colors = Colors::default();
+        for (name, meridiem) in [("time_12h_en", Meridiem::En), ("time_12h_ja", Meridiem::Ja)] {
+            let format = TimeFormat {
+                hour_format: HourFormat::Twelve,
+                meridiem,
+            };
+            // 1桁の時 (21:05 → 9:05) と 2桁の時 (0:30 → 12:30) で中央寄せを確認
+            let mut fb = frame(400, 240);
+            for (row, (hour, minute)) in [(21, 5), (0, 30)].into_iter().enumerate() {
+                let region = Rect::new(0, 10 + 120 * row as i32, 400, 100);
+                draw_time(&mut fb, &region, hour, minute, true, format, colors.time);
+            }
+            assert_snapshot(name, &fb);
+        }
+    }
+
+    #[test]
+    fn forecast_12_hour() {
+        let mut fb = frame(720, 128);
+        let weather = weather();
+        let theme = Theme {
+            time_format: TimeFormat {
+                hour_format: HourFormat::Twelve,
+                meridiem: Meridiem::Ja,
+            },
+            ..Default::default()
+        };
+        draw_forecast(&mut fb, &Rect::new(0, 4, 720, 120), &weather.hours, &theme);
+        assert_snapshot("forecast_12h_ja", &fb);
+    }
+
+    #[test]
+    fn forecast() {
+        let mut fb = frame(720, 128);
+        let weather = weather();
+        draw_forecast(
+            &mut fb,
+            &Rect::new(0, 4, 720, 120),
+            &weather.hours,
+            &Theme::default(),
+        );
+        assert_snapshot("forecast", &fb);
+    }
+
+    #[test]
+    fn weather_icons() {
+        let icons = [
+            WeatherIconType::Sun,
+            WeatherIconType::Moon,
+            WeatherIconType::Cloud,
+            WeatherIconType::Rain,
+            WeatherIconType::HeavyRain,
+            WeatherIconType::Snow,
+            WeatherIconType::Thunder,
+        ];
+        let size = ICON_SIZE * 2;
+        let mut fb = frame((size + 4) as usize * icons.len() + 4, size as usize + 8);
+        for (i, icon) in icons.into_iter().enumerate() {
+            draw_weather_icon(&mut fb, icon, 4 + i as i32 * (size + 4), 4, 2);
+        }
+        assert_snapshot("weather_icons", &fb);
+    }
+
+    #[test]
+    fn whole_scene() {
+        let weather = weather();
+        let view = WeatherView {
+            weather: Some(&weather),
+            ..Default::default()
+        };
+        let now = clock().now();
+        let ja = Theme {
+            locale: Locale::Ja,
+            ..Default::default()
+        };
+        for (name, width, height, theme) in [
+            ("scene_800x480", 800, 480, &Theme::default()),
+            ("scene_480x320", 480, 320, &Theme::default()),
+            ("scene_800x480_ja", 800, 480, &ja),
+        ] {
+            let mut fb = FrameBuffer::new(width, height);
+            draw_scene(&mut fb, &now, &view, theme);
+            assert_snapshot(name, &fb);
+        }
+    }
+
+    // 設定ファイルで並べ替えた画面（横並びと格子）
+    #[test]
+    fn custom_screen() {
+        let config = Config::parse(
+            r#"
+[screen]
+type = "column"
+children = [
+  { type = "spacer", size = 1 },
+  { type = "row", size = 4, children = [
+    { type = "time", size = 3 },
+    { type = "column", size = 2, children = [{ type = "date" }, { type = "current" }] },
+  ] },
+  { type = "grid", size = 5, columns = 2, children = [
+    { type = "summary" },
+    { type = "forecast" },
+  ] },
+]
+"#,
+        )
+        .unwrap();
+        let theme = config.theme().unwrap();
+        let weather = weather();
+        let view = WeatherView {
+            weather: Some(&weather),
+            ..Default::default()
+        };
+        let mut fb = FrameBuffer::new(800, 480);
+        draw_scene(&mut fb, &clock().now(), &view, &theme);
+        assert_snapshot("scene_custom", &fb);
+    }
+}
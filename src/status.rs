@@ -0,0 +1,13 @@
+use embedded_graphics::pixelcolor::Rgb888;
+
+use crate::font::Font;
+use crate::framebuffer::FrameBuffer;
+use crate::layout::Rect;
+use crate::text::{Align, TextStyle};
+
+// 天気取得の失敗などを region の右端に小さく表示
+pub fn draw_status(fb: &mut FrameBuffer, region: &Rect, font: &Font, label: &str, color: Rgb888) {
+    TextStyle::fit(font, label, region)
+        .align(Align::Right)
+        .draw(fb, label, region, color);
+}
@@ -0,0 +1,122 @@
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::RgbColor;
+use serde::{Deserialize, Deserializer};
+use std::str::FromStr;
+
+use crate::config::parse_color;
+
+// 小数点以下を四捨五入した整数値で表示 (例: "-3°")
+pub fn temperature_text(temp_c: f64) -> String {
+    format!("{}°", temp_c.round() as i32)
+}
+
+// 気温に応じた表示色（寒い=青 → 暑い=赤）。基準点の間は線形補間する
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemperatureScale {
+    // 気温の昇順に並んだ (気温, 色)
+    stops: Vec<(f64, Rgb888)>,
+}
+
+impl TemperatureScale {
+    pub fn new(mut stops: Vec<(f64, Rgb888)>) -> Self {
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    pub fn color_for(&self, temp_c: f64) -> Rgb888 {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Rgb888::WHITE,
+        };
+        // NaN（取得元の欠損値など）は最も低い気温の色にする
+        if temp_c.is_nan() || temp_c <= first.0 {
+            return first.1;
+        }
+        if temp_c >= last.0 {
+            return last.1;
+        }
+
+        // 基準点に NaN が混ざっていると見つからないことがある
+        let Some(upper) = self.stops.iter().position(|s| s.0 >= temp_c) else {
+            return last.1;
+        };
+        let (t0, c0) = self.stops[upper - 1];
+        let (t1, c1) = self.stops[upper];
+        let ratio = (temp_c - t0) / (t1 - t0);
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+        Rgb888::new(
+            lerp(c0.r(), c1.r()),
+            lerp(c0.g(), c1.g()),
+            lerp(c0.b(), c1.b()),
+        )
+    }
+}
+
+impl Default for TemperatureScale {
+    fn default() -> Self {
+        Self::new(vec![
+            (0.0, Rgb888::new(90, 140, 255)),
+            (10.0, Rgb888::new(140, 210, 255)),
+            (20.0, Rgb888::new(220, 220, 220)),
+            (28.0, Rgb888::new(255, 170, 60)),
+            (35.0, Rgb888::new(255, 70, 60)),
+        ])
+    }
+}
+
+// "0:#5a8cff,20:#dcdcdc,35:#ff463c" 形式（気温:色 をカンマ区切り）
+impl FromStr for TemperatureScale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let stops = s
+            .split(',')
+            .map(|stop| {
+                let invalid = || format!("気温スケールの形式が不正です: {}", stop.trim());
+                let (temp, color) = stop.trim().split_once(':').ok_or_else(invalid)?;
+                // "nan" や "inf" も f64 としては読めるので、有限の値だけを受け付ける
+                let temp = temp
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|temp| temp.is_finite())
+                    .ok_or_else(invalid)?;
+                Ok((temp, parse_color(color).ok_or_else(invalid)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self::new(stops))
+    }
+}
+
+// 設定ファイルでは FromStr と同じ文字列形式で指定する
+impl<'de> Deserialize<'de> for TemperatureScale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_are_interpolated_and_clamped() {
+        let scale: TemperatureScale = "0:#000000,10:#0000ff".parse().unwrap();
+        assert_eq!(scale.color_for(5.0), Rgb888::new(0, 0, 128));
+        assert_eq!(scale.color_for(-20.0), Rgb888::new(0, 0, 0));
+        assert_eq!(scale.color_for(f64::INFINITY), Rgb888::new(0, 0, 255));
+        assert_eq!(scale.color_for(f64::NAN), Rgb888::new(0, 0, 0));
+
+        // 16進数以外の文字（from_str_radix が受け付ける符号も）は不可
+        assert!("0:#+12345".parse::<TemperatureScale>().is_err());
+        // 基準点の気温は有限の値だけ
+        assert!("0:#000000,nan:#ffffff".parse::<TemperatureScale>().is_err());
+        assert!("0:#000000,inf:#ffffff".parse::<TemperatureScale>().is_err());
+        let scale = TemperatureScale::new(vec![
+            (0.0, Rgb888::new(0, 0, 0)),
+            (f64::NAN, Rgb888::new(255, 255, 255)),
+        ]);
+        assert_eq!(scale.color_for(5.0), Rgb888::new(255, 255, 255));
+    }
+}
@@ -0,0 +1,136 @@
+// 文字列の計測と、領域内での揃え・切り抜きをまとめた描画
+use embedded_graphics::{
+    draw_target::DrawTargetExt,
+    pixelcolor::Rgb888,
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+
+use crate::font::Font;
+use crate::framebuffer::FrameBuffer;
+use crate::layout::{fit_pixel_size, Rect};
+
+// 領域内での横方向の揃え
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+// フォント・大きさ・揃えの組
+#[derive(Clone, Copy)]
+pub struct TextStyle<'a> {
+    pub font: &'a Font,
+    pub pixel_size: i32,
+    pub align: Align,
+}
+
+impl<'a> TextStyle<'a> {
+    pub fn new(font: &'a Font, pixel_size: i32) -> Self {
+        Self {
+            font,
+            pixel_size,
+            align: Align::Left,
+        }
+    }
+
+    // text が region に収まる最大の大きさ（最低 1）
+    pub fn fit(font: &'a Font, text: &str, region: &Rect) -> Self {
+        let pixel_size = fit_pixel_size(font.text_width(text), font.height, region);
+        Self::new(font, pixel_size)
+    }
+
+    pub fn align(self, align: Align) -> Self {
+        Self { align, ..self }
+    }
+
+    // 描画したときの幅（画素）
+    pub fn width(&self, text: &str) -> i32 {
+        self.font.text_width(text) * self.pixel_size
+    }
+
+    // 1 行の高さ（画素）
+    pub fn height(&self) -> i32 {
+        self.font.height * self.pixel_size
+    }
+
+    // text の後に続けて描画するときの位置までの幅（画素）
+    pub fn advance(&self, text: &str) -> i32 {
+        self.font.advance(text) * self.pixel_size
+    }
+
+    // (x, y) を左上にして描画
+    pub fn draw_at(&self, fb: &mut FrameBuffer, text: &str, x: i32, y: i32, color: Rgb888) {
+        self.font.draw_text(fb, text, x, y, self.pixel_size, color);
+    }
+
+    // region の上端に揃えて描画し、左端の X 座標を返す。region からはみ出した部分は描かない
+    pub fn draw(&self, fb: &mut FrameBuffer, text: &str, region: &Rect, color: Rgb888) -> i32 {
+        self.draw_spans(fb, &[(text, color)], region)
+    }
+
+    // 色の異なる文字列を続けて 1 行として描画する
+    pub fn draw_spans(&self, fb: &mut FrameBuffer, spans: &[(&str, Rgb888)], region: &Rect) -> i32 {
+        let text: String = spans.iter().map(|(text, _)| *text).collect();
+        let width = self.width(&text);
+        let left = match self.align {
+            Align::Left => region.x,
+            // 中央から幅の半分だけ左（以前の描画処理と同じ丸め）
+            Align::Center => region.x + region.width / 2 - width / 2,
+            Align::Right => region.x + region.width - width,
+        };
+
+        let bounds = Rectangle::new(
+            Point::new(region.x, region.y),
+            Size::new(region.width.max(0) as u32, region.height.max(0) as u32),
+        );
+        let mut clipped = fb.clipped(&bounds);
+        let mut x = left;
+        for (text, color) in spans {
+            self.font
+                .draw_text(&mut clipped, text, x, region.y, self.pixel_size, *color);
+            x += self.advance(text);
+        }
+        left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligns_and_clips_within_region() {
+        let font = Font::builtin();
+        let style = TextStyle::new(font, 2);
+        // "AB" = 8 + 1 + 8
+        assert_eq!(style.width("AB"), 34);
+        assert_eq!(style.height(), 24);
+
+        let mut fb = FrameBuffer::new(100, 30);
+        let region = Rect::new(10, 0, 80, 30);
+        assert_eq!(
+            style.draw(&mut fb, "AB", &region, Rgb888::new(255, 255, 255)),
+            10
+        );
+        let centered = style.align(Align::Center);
+        assert_eq!(
+            centered.draw(&mut fb, "AB", &region, Rgb888::new(255, 255, 255)),
+            33
+        );
+        let right = style.align(Align::Right);
+        assert_eq!(
+            right.draw(&mut fb, "AB", &region, Rgb888::new(255, 255, 255)),
+            56
+        );
+
+        // 収まらない文字列は region の外に描かない
+        let mut fb = FrameBuffer::new(100, 30);
+        let narrow = Rect::new(0, 0, 20, 30);
+        style.draw(&mut fb, "WWWW", &narrow, Rgb888::new(255, 255, 255));
+        let drawn = |x: usize| (0..30).any(|y| fb.buffer[y * 100 + x] != 0);
+        assert!(drawn(0) && !drawn(20) && !drawn(40));
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone};
+use std::time::Instant;
+
+// 現在時刻の取得元（テストや早送り表示のために差し替えられる）
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Local>;
+}
+
+// システムの時計
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+// 常に同じ時刻を返す
+pub struct FixedClock(pub DateTime<Local>);
+
+impl FixedClock {
+    // ローカル時刻で指定（夏時間の切り替えで重複する時刻は早い方）
+    pub fn at(local: NaiveDateTime) -> Option<Self> {
+        Local.from_local_datetime(&local).earliest().map(Self)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Local> {
+        self.0
+    }
+}
+
+// start から speed 倍の速さで進む時計（タイムラプス表示用）
+pub struct SimulatedClock {
+    start: DateTime<Local>,
+    started: Instant,
+    speed: f64,
+}
+
+impl SimulatedClock {
+    pub fn new(start: DateTime<Local>, speed: f64) -> Self {
+        Self {
+            start,
+            started: Instant::now(),
+            speed,
+        }
+    }
+
+    fn now_at(&self, instant: Instant) -> DateTime<Local> {
+        let elapsed = instant.duration_since(self.started).mul_f64(self.speed);
+        TimeDelta::from_std(elapsed)
+            .ok()
+            .and_then(|elapsed| self.start.checked_add_signed(elapsed))
+            .unwrap_or(self.start)
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> DateTime<Local> {
+        self.now_at(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn simulated_clock_runs_at_given_speed() {
+        let start = Local.with_ymd_and_hms(2026, 1, 12, 23, 59, 0).unwrap();
+        let clock = SimulatedClock::new(start, 60.0);
+
+        let later = clock.now_at(clock.started + Duration::from_secs(2));
+        assert_eq!(later, Local.with_ymd_and_hms(2026, 1, 13, 0, 1, 0).unwrap());
+        assert!(clock.now() >= start);
+    }
+}
@@ -1,131 +1,216 @@
-use dotenv::dotenv;
-use embedded_graphics::{
-    pixelcolor::Rgb888,
-    prelude::*,
-    primitives::{PrimitiveStyle, Rectangle},
-};
+mod jma;
+mod open_meteo;
+#[cfg(test)]
+mod test_server;
+mod weatherapi;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
-use std::env;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
 
-use crate::framebuffer::FrameBuffer;
+use crate::cache;
+use crate::config::WeatherConfig;
 
-// 天気APIのレスポンス全体
-#[derive(Debug, Deserialize, Serialize)]
-pub struct WeatherReaponse {
-    pub location: Location,
-    pub current: Current,
-    pub forecast: Forecast,
-}
+pub use jma::Jma;
+pub use open_meteo::OpenMeteo;
+pub use weatherapi::WeatherApi;
 
-// 場所情報
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Location {
-    pub name: String,
-    pub region: String,
-    pub country: String,
-    pub lat: f64,
-    pub lon: f64,
-    pub tz_id: String,
-    pub localtime_epoch: i64,
-    pub localtime: String,
-}
-
-// 現在の天気情報
-#[derive(Debug, Deserialize, Serialize)]
+// 現在の天気
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Current {
     pub temp_c: f64,
-    pub is_day: u8,
-    pub condition: Condition,
-}
-
-// 天気状態
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Condition {
-    pub code: u16,
+    pub icon: WeatherIconType,
 }
 
-// 予報情報
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Forecast {
-    pub forecastday: Vec<ForecastDay>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct ForecastDay {
-    pub date: String,
-    pub date_epoch: i64,
-    pub day: Day,
-    pub hour: Vec<Hour>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Day {}
-
 // 時間ごとの予報
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Hour {
-    pub time: String,
+    // 予報地点の現地時刻
+    pub time: NaiveDateTime,
     pub time_epoch: i64,
-    pub temp_c: f64,
-    pub is_day: u8,
+    // 時間ごとの気温がない取得元では None
+    pub temp_c: Option<f64>,
     pub chance_of_rain: u8,
-    pub condition: Condition,
+    pub icon: WeatherIconType,
 }
 
-// 天気情報加工後のJSON
-#[derive(Debug, Deserialize, Serialize)]
+// 日ごとの概要
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    // 発表時刻によっては当日の最低気温などがない
+    pub maxtemp_c: Option<f64>,
+    pub mintemp_c: Option<f64>,
+    // 白夜・極夜などで存在しない日は None
+    pub sunrise: Option<NaiveTime>,
+    pub sunset: Option<NaiveTime>,
+}
+
+// 天気情報（取得元によらない共通の形式）
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Weather {
     pub current: Current,
-    pub forecast: Vec<Hour>,
+    // 取得できた全時間分（表示時に現在時刻以降を選ぶ）
+    pub hours: Vec<Hour>,
+    pub days: Vec<DaySummary>,
 }
 
 impl Weather {
-    fn parse_weather(response: WeatherReaponse) -> Weather {
-        let localtime_epoch = response.location.localtime_epoch;
-
-        let forecast: Vec<Hour> = response
-            .forecast
-            .forecastday
-            .into_iter()
-            .flat_map(|day| day.hour)
-            .filter(|h| h.time_epoch >= localtime_epoch)
-            .take(4)
-            .collect();
+    // now_epoch (UNIX 秒) 以降の予報を count 件
+    pub fn upcoming(&self, now_epoch: i64, count: usize) -> &[Hour] {
+        let start = self
+            .hours
+            .iter()
+            .position(|h| h.time_epoch >= now_epoch)
+            .unwrap_or(self.hours.len());
+        let end = (start + count).min(self.hours.len());
+        &self.hours[start..end]
+    }
+
+    // 指定日の概要
+    pub fn day(&self, date: NaiveDate) -> Option<&DaySummary> {
+        self.days.iter().find(|d| d.date == date)
+    }
+}
+
+// 天気取得の失敗理由
+#[derive(Debug)]
+pub enum WeatherError {
+    // 必要な環境変数が設定されていない
+    MissingConfig(&'static str),
+    // 設定値が不正
+    InvalidConfig(String),
+    // 接続失敗・タイムアウトなど
+    Network(reqwest::Error),
+    // API が成功以外のステータスを返した
+    HttpStatus(reqwest::StatusCode),
+    // API の呼び出し回数上限に達した
+    Quota,
+    // レスポンスの JSON が想定と異なる
+    Decode(serde_json::Error),
+}
+
+impl WeatherError {
+    // 画面の状態表示用の短い表記
+    pub fn short_label(&self) -> String {
+        match self {
+            WeatherError::MissingConfig(_) => "NO CONFIG".to_string(),
+            WeatherError::InvalidConfig(_) => "BAD CONFIG".to_string(),
+            WeatherError::Network(e) if e.is_timeout() => "TIMEOUT".to_string(),
+            WeatherError::Network(_) => "OFFLINE".to_string(),
+            WeatherError::HttpStatus(status) => format!("HTTP {}", status.as_u16()),
+            WeatherError::Quota => "QUOTA".to_string(),
+            WeatherError::Decode(_) => "BAD DATA".to_string(),
+        }
+    }
+}
 
-        Weather {
-            current: response.current,
-            forecast,
+impl std::fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeatherError::MissingConfig(name) => write!(f, "{} が設定されていません", name),
+            WeatherError::InvalidConfig(message) => write!(f, "{}", message),
+            WeatherError::Network(e) => write!(f, "通信に失敗しました: {}", e),
+            WeatherError::HttpStatus(status) => write!(f, "API がエラーを返しました: {}", status),
+            WeatherError::Quota => write!(f, "API の呼び出し上限に達しました"),
+            WeatherError::Decode(e) => write!(f, "レスポンスの解析に失敗しました: {}", e),
         }
     }
 }
 
-pub async fn get_weather() -> Result<Weather, Box<dyn std::error::Error + Send + Sync>> {
-    let response = fetch_weather().await?;
-    Ok(Weather::parse_weather(response))
+impl std::error::Error for WeatherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WeatherError::Network(e) => Some(e),
+            WeatherError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
-pub async fn fetch_weather() -> Result<WeatherReaponse, Box<dyn std::error::Error + Send + Sync>> {
-    dotenv().ok();
+pub type WeatherFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Weather, WeatherError>> + Send + 'a>>;
+
+// 天気の取得元
+pub trait WeatherProvider: Send + Sync {
+    fn fetch(&self) -> WeatherFuture<'_>;
+}
 
-    let api_key = env::var("WEATHERAPI_KEY")?;
-    let location = env::var("WEATHER_LOCATION")?;
+// 天気APIの応答待ちの上限
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
 
-    let url = format!(
-        "http://api.weatherapi.com/v1/forecast.json?key={}&q={}&days=2",
-        api_key, location
-    );
+// GET して本文を返す。成功以外のステータスは on_error で取得元ごとに判別
+async fn get_body(
+    url: &str,
+    on_error: fn(reqwest::StatusCode, &str) -> WeatherError,
+) -> Result<String, WeatherError> {
+    // URL に API キーが含まれることがあるため、エラーには URL を残さない
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| WeatherError::Network(e.without_url()))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| WeatherError::Network(e.without_url()))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| WeatherError::Network(e.without_url()))?;
+    if !status.is_success() {
+        return Err(on_error(status, &body));
+    }
+    Ok(body)
+}
 
-    let response = reqwest::get(&url)
-        .await?
-        .error_for_status()?
-        .json::<WeatherReaponse>()
-        .await?;
+// 呼び出し上限 (429) 以外はステータスをそのまま返す
+fn status_error(status: reqwest::StatusCode, _body: &str) -> WeatherError {
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        WeatherError::Quota
+    } else {
+        WeatherError::HttpStatus(status)
+    }
+}
+
+// UNIX 秒を utc_offset 秒ずらした現地時刻に変換
+fn local_time(epoch: i64, utc_offset: i32) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp(epoch + utc_offset as i64, 0).map(|t| t.naive_utc())
+}
+
+// 設定ファイルの [weather] から取得元を作る
+pub fn provider(config: &WeatherConfig) -> Result<Box<dyn WeatherProvider>, WeatherError> {
+    match config {
+        WeatherConfig::Weatherapi { location, api_key } => {
+            let location = location.as_deref().ok_or(WeatherError::MissingConfig(
+                "weather.location (WEATHER_LOCATION)",
+            ))?;
+            let api_key = api_key
+                .as_deref()
+                .ok_or(WeatherError::MissingConfig("WEATHERAPI_KEY"))?;
+            Ok(Box::new(WeatherApi::new(api_key, location)))
+        }
+        WeatherConfig::OpenMeteo {
+            latitude,
+            longitude,
+        } => Ok(Box::new(OpenMeteo::new(*latitude, *longitude))),
+        WeatherConfig::Jma { office, area } => Ok(Box::new(Jma::new(office, area.as_deref()))),
+    }
+}
 
-    Ok(response)
+// 天気を取得し、次回起動時にすぐ表示できるよう保存しておく
+// 取得時刻は --at / --speed の時計ではなく実際の時刻で記録する（デモ実行の後も古さを正しく判定するため）
+pub async fn get_weather(provider: &dyn WeatherProvider) -> Result<Weather, WeatherError> {
+    let weather = provider.fetch().await?;
+    Ok(cache::store(weather, &Local::now()))
 }
 
 // 天気アイコンの種類
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum WeatherIconType {
     Sun,
     Moon,
@@ -136,80 +221,61 @@ pub enum WeatherIconType {
     Thunder,
 }
 
-// 天気コードからアイコンを取得
-pub fn get_weather_icon(code: u16, is_day: bool) -> WeatherIconType {
-    match code {
-        // 晴れ
-        1000 => {
-            if is_day {
-                WeatherIconType::Sun
-            } else {
-                WeatherIconType::Moon
-            }
+impl WeatherIconType {
+    // 晴れのアイコン（夜は月）
+    fn clear(is_day: bool) -> Self {
+        if is_day {
+            WeatherIconType::Sun
+        } else {
+            WeatherIconType::Moon
         }
-        // 曇り、霧（晴れ時々曇りも含む）
-        1003 | 1006 | 1009 | 1030 | 1135 | 1147 => WeatherIconType::Cloud,
-        // 小雨〜並の雨
-        1063 | 1072 | 1150 | 1153 | 1168 | 1180 | 1183 | 1186 | 1189 | 1198 | 1240 => {
-            WeatherIconType::Rain
-        }
-        // 大雨、激しい雨
-        1171 | 1192 | 1195 | 1201 | 1243 | 1246 => WeatherIconType::HeavyRain,
-        // 雪、みぞれ、あられ
-        1066 | 1069 | 1114 | 1117 | 1204 | 1207 | 1210 | 1213 | 1216 | 1219 | 1222 | 1225
-        | 1237 | 1249 | 1252 | 1255 | 1258 | 1261 | 1264 => WeatherIconType::Snow,
-        // 雷
-        1087 | 1273 | 1276 | 1279 | 1282 => WeatherIconType::Thunder,
-        _ => WeatherIconType::Cloud,
     }
 }
 
-// PNG アイコンデータ（コンパイル時に埋め込み）
-const ICON_SUN_PNG: &[u8] = include_bytes!("../assets/icons/sun.png");
-const ICON_MOON_PNG: &[u8] = include_bytes!("../assets/icons/moon.png");
-const ICON_CLOUD_PNG: &[u8] = include_bytes!("../assets/icons/cloud.png");
-const ICON_RAIN_PNG: &[u8] = include_bytes!("../assets/icons/rain.png");
-const ICON_HEAVY_RAIN_PNG: &[u8] = include_bytes!("../assets/icons/heavy_rain.png");
-const ICON_SNOW_PNG: &[u8] = include_bytes!("../assets/icons/snow.png");
-const ICON_THUNDER_PNG: &[u8] = include_bytes!("../assets/icons/thunder.png");
-
-fn draw_pixel(fb: &mut FrameBuffer, x: i32, y: i32, size: i32, color: Rgb888) {
-    Rectangle::new(Point::new(x, y), Size::new(size as u32, size as u32))
-        .into_styled(PrimitiveStyle::with_fill(color))
-        .draw(fb)
-        .unwrap();
-}
-
-fn draw_png_icon(fb: &mut FrameBuffer, png_data: &[u8], x: i32, y: i32, scale: i32) {
-    use image::GenericImageView;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_source::{Clock, FixedClock};
+    use chrono::{Datelike, TimeZone, Timelike};
 
-    let img = image::load_from_memory(png_data).expect("Failed to load PNG icon");
-
-    for (px, py, pixel) in img.pixels() {
-        let [r, g, b, a] = pixel.0;
-        // 透明度が128以上のピクセルのみ描画
-        if a > 128 {
-            draw_pixel(
-                fb,
-                x + (px as i32) * scale,
-                y + (py as i32) * scale,
-                scale,
-                Rgb888::new(r, g, b),
-            );
+    fn hour_at(day: u32, hour: u32) -> Hour {
+        let time = Local.with_ymd_and_hms(2026, 1, day, hour, 0, 0).unwrap();
+        Hour {
+            time: time.naive_local(),
+            time_epoch: time.timestamp(),
+            temp_c: Some(hour as f64),
+            chance_of_rain: 0,
+            icon: WeatherIconType::Moon,
         }
     }
-}
 
-pub fn draw_weather_icon(fb: &mut FrameBuffer, icon: WeatherIconType, x: i32, y: i32, scale: i32) {
-    let png_data = match icon {
-        WeatherIconType::Sun => ICON_SUN_PNG,
-        WeatherIconType::Moon => ICON_MOON_PNG,
-        WeatherIconType::Cloud => ICON_CLOUD_PNG,
-        WeatherIconType::Rain => ICON_RAIN_PNG,
-        WeatherIconType::HeavyRain => ICON_HEAVY_RAIN_PNG,
-        WeatherIconType::Snow => ICON_SNOW_PNG,
-        WeatherIconType::Thunder => ICON_THUNDER_PNG,
-    };
+    #[test]
+    fn upcoming_at_2330_crosses_into_tomorrow() {
+        let hours = (20..24)
+            .map(|h| hour_at(12, h))
+            .chain((0..4).map(|h| hour_at(13, h)))
+            .collect();
+        let weather = Weather {
+            current: Current {
+                temp_c: 3.0,
+                icon: WeatherIconType::Moon,
+            },
+            hours,
+            days: Vec::new(),
+        };
 
-    draw_png_icon(fb, png_data, x, y, scale);
+        let clock = FixedClock::at(
+            NaiveDate::from_ymd_opt(2026, 1, 12)
+                .unwrap()
+                .and_hms_opt(23, 30, 0)
+                .unwrap(),
+        )
+        .unwrap();
+        let upcoming = weather.upcoming(clock.now().timestamp(), 4);
+        let times: Vec<(u32, u32)> = upcoming
+            .iter()
+            .map(|h| (h.time.day(), h.time.hour()))
+            .collect();
+        assert_eq!(times, vec![(13, 0), (13, 1), (13, 2), (13, 3)]);
+    }
 }
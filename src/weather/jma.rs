@@ -0,0 +1,411 @@
+// 気象庁 (jma.go.jp) の府県天気予報からの取得（API キー不要）
+use chrono::{DateTime, FixedOffset, NaiveDate, Timelike};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::{
+    get_body, status_error, Current, DaySummary, Hour, Weather, WeatherError, WeatherFuture,
+    WeatherIconType, WeatherProvider,
+};
+
+const BASE_URL: &str = "https://www.jma.go.jp/bosai";
+
+// forecast/{府県予報区}.json は [短期予報, 週間予報] の配列
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForecastReport {
+    time_series: Vec<TimeSeries>,
+}
+
+// 時刻の並びと、地域ごとの値（数値も文字列、値がなければ ""）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeSeries {
+    time_defines: Vec<DateTime<FixedOffset>>,
+    areas: Vec<AreaValues>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AreaValues {
+    area: Area,
+    #[serde(default)]
+    weather_codes: Vec<String>,
+    #[serde(default)]
+    pops: Vec<String>,
+    #[serde(default)]
+    temps: Vec<String>,
+    #[serde(default)]
+    temps_min: Vec<String>,
+    #[serde(default)]
+    temps_max: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Area {
+    code: String,
+}
+
+// アメダスの観測値。各要素は [値, 品質情報]
+#[derive(Debug, Deserialize)]
+struct Observation {
+    temp: Option<(Option<f64>, u8)>,
+}
+
+fn value<T: std::str::FromStr>(values: &[String], i: usize) -> Option<T> {
+    values.get(i)?.parse().ok()
+}
+
+// 指定した値を持つ時系列を探す（発表時刻によって時系列の数が変わるため位置では決めない）
+fn series(report: &ForecastReport, has: impl Fn(&AreaValues) -> bool) -> Option<&TimeSeries> {
+    report
+        .time_series
+        .iter()
+        .find(|ts| ts.areas.first().is_some_and(&has))
+}
+
+fn decode_error(message: &str) -> WeatherError {
+    WeatherError::Decode(serde::de::Error::custom(message))
+}
+
+// 気象庁の天気コードからアイコンを取得（先頭の数字が主な天気: 1 晴れ, 2 曇り, 3 雨, 4 雪）
+pub fn get_weather_icon(code: u16, is_day: bool) -> WeatherIconType {
+    match code {
+        // 雨で雷を伴う、雪で雷を伴う、晴/曇時々雨で雷を伴う
+        140 | 240 | 250 | 350 | 450 => WeatherIconType::Thunder,
+        // 大雨、暴風雨、雨一時強く降る
+        306 | 308 | 328 => WeatherIconType::HeavyRain,
+        // 雪か雨
+        340 => WeatherIconType::Snow,
+        100..=199 => WeatherIconType::clear(is_day),
+        200..=299 => WeatherIconType::Cloud,
+        300..=399 => WeatherIconType::Rain,
+        400..=499 => WeatherIconType::Snow,
+        _ => WeatherIconType::Cloud,
+    }
+}
+
+// 6時〜18時を昼とみなす
+fn is_daytime(hour: u32) -> bool {
+    (6..18).contains(&hour)
+}
+
+// 予報区1つ分の予報（現在の気温はアメダスから別に取得する）
+#[derive(Debug)]
+struct AreaForecast {
+    hours: Vec<Hour>,
+    days: Vec<DaySummary>,
+    // 日ごとの天気コード
+    codes: BTreeMap<NaiveDate, u16>,
+    // 気温の予報に使われているアメダス地点
+    station: Option<String>,
+}
+
+impl AreaForecast {
+    fn into_weather(self, temp_c: f64, observed_at: DateTime<FixedOffset>) -> Weather {
+        let code = self
+            .codes
+            .range(..=observed_at.date_naive())
+            .next_back()
+            .map(|(_, &code)| code)
+            .unwrap_or(200);
+        Weather {
+            current: Current {
+                temp_c,
+                icon: get_weather_icon(code, is_daytime(observed_at.hour())),
+            },
+            hours: self.hours,
+            days: self.days,
+        }
+    }
+}
+
+// area が None なら府県予報区の最初の地域
+fn parse_forecast(
+    reports: &[ForecastReport],
+    area: Option<&str>,
+) -> Result<AreaForecast, WeatherError> {
+    let short = reports
+        .first()
+        .ok_or_else(|| decode_error("短期予報がありません"))?;
+    let weather_series = series(short, |a| !a.weather_codes.is_empty())
+        .ok_or_else(|| decode_error("天気コードがありません"))?;
+    let index = match area {
+        Some(code) => weather_series
+            .areas
+            .iter()
+            .position(|a| a.area.code == code)
+            .ok_or_else(|| {
+                WeatherError::InvalidConfig(format!("予報区 {} が見つかりません", code))
+            })?,
+        None => 0,
+    };
+    let area_weather = &weather_series.areas[index];
+    let area_code = &area_weather.area.code;
+
+    let mut codes = BTreeMap::new();
+    for (i, time) in weather_series.time_defines.iter().enumerate() {
+        if let Some(code) = value(&area_weather.weather_codes, i) {
+            codes.insert(time.date_naive(), code);
+        }
+    }
+
+    // 最高・最低気温。短期予報の 0 時は最低気温、9 時は最高気温
+    let mut temps: BTreeMap<NaiveDate, (Option<f64>, Option<f64>)> = BTreeMap::new();
+    let mut station = None;
+    if let Some(ts) = series(short, |a| !a.temps.is_empty()) {
+        // 気温の地点は予報区と同じ順に並んでいる
+        if let Some(point) = ts.areas.get(index) {
+            station = Some(point.area.code.clone());
+            for (i, time) in ts.time_defines.iter().enumerate() {
+                let entry = temps.entry(time.date_naive()).or_default();
+                match time.hour() {
+                    0 => entry.1 = value(&point.temps, i),
+                    _ => entry.0 = value(&point.temps, i),
+                }
+            }
+        }
+    }
+
+    // 週間予報で短期予報にない日を補う
+    if let Some(weekly) = reports.get(1) {
+        if let Some(ts) = series(weekly, |a| !a.weather_codes.is_empty()) {
+            let weekly_index = ts
+                .areas
+                .iter()
+                .position(|a| &a.area.code == area_code)
+                .unwrap_or(0);
+            for (i, time) in ts.time_defines.iter().enumerate() {
+                if let Some(code) = value(&ts.areas[weekly_index].weather_codes, i) {
+                    codes.entry(time.date_naive()).or_insert(code);
+                }
+            }
+
+            if let Some(temp_series) = series(weekly, |a| !a.temps_max.is_empty()) {
+                let point = temp_series
+                    .areas
+                    .iter()
+                    .find(|a| Some(&a.area.code) == station.as_ref())
+                    .or_else(|| temp_series.areas.get(weekly_index));
+                if let Some(point) = point {
+                    for (i, time) in temp_series.time_defines.iter().enumerate() {
+                        let entry = temps.entry(time.date_naive()).or_default();
+                        entry.0 = entry.0.or(value(&point.temps_max, i));
+                        entry.1 = entry.1.or(value(&point.temps_min, i));
+                    }
+                }
+            }
+        }
+    }
+
+    // 降水確率は6時間ごと。各時間帯の開始時刻の予報として扱う
+    let mut hours = Vec::new();
+    if let Some(ts) = series(short, |a| !a.pops.is_empty()) {
+        if let Some(pops) = ts.areas.iter().find(|a| &a.area.code == area_code) {
+            for (i, time) in ts.time_defines.iter().enumerate() {
+                let (Some(pop), Some(&code)) =
+                    (value(&pops.pops, i), codes.get(&time.date_naive()))
+                else {
+                    continue;
+                };
+                hours.push(Hour {
+                    time: time.naive_local(),
+                    time_epoch: time.timestamp(),
+                    temp_c: None,
+                    chance_of_rain: pop,
+                    icon: get_weather_icon(code, is_daytime(time.hour())),
+                });
+            }
+        }
+    }
+
+    // 日の出・日の入りは予報に含まれない
+    let days = codes
+        .keys()
+        .map(|&date| {
+            let (maxtemp_c, mintemp_c) = temps.get(&date).copied().unwrap_or_default();
+            DaySummary {
+                date,
+                maxtemp_c,
+                mintemp_c,
+                sunrise: None,
+                sunset: None,
+            }
+        })
+        .collect();
+
+    Ok(AreaForecast {
+        hours,
+        days,
+        codes,
+        station,
+    })
+}
+
+// 気象庁の設定（府県予報区と、その中の一次細分区域のコード）
+pub struct Jma {
+    base_url: String,
+    // 例: "130000"（東京都）
+    office: String,
+    // 例: "130010"（東京地方）
+    area: Option<String>,
+}
+
+impl Jma {
+    pub fn new(office: &str, area: Option<&str>) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            office: office.to_string(),
+            area: area.map(str::to_string),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    async fn fetch_forecast(&self) -> Result<Vec<ForecastReport>, WeatherError> {
+        let url = format!(
+            "{}/forecast/data/forecast/{}.json",
+            self.base_url, self.office
+        );
+        let body = get_body(&url, status_error).await?;
+        serde_json::from_str(&body).map_err(WeatherError::Decode)
+    }
+
+    // 最新のアメダス観測時刻と、その時刻の地点の気温
+    async fn fetch_temperature(
+        &self,
+        station: &str,
+    ) -> Result<(f64, DateTime<FixedOffset>), WeatherError> {
+        let url = format!("{}/amedas/data/latest_time.txt", self.base_url);
+        let latest = get_body(&url, status_error).await?;
+        let observed_at = DateTime::parse_from_rfc3339(latest.trim())
+            .map_err(|_| decode_error("アメダスの観測時刻を解釈できません"))?;
+
+        let url = format!(
+            "{}/amedas/data/map/{}.json",
+            self.base_url,
+            observed_at.format("%Y%m%d%H%M%S")
+        );
+        let body = get_body(&url, status_error).await?;
+        let observations: HashMap<String, Observation> =
+            serde_json::from_str(&body).map_err(WeatherError::Decode)?;
+        let temp_c = observations
+            .get(station)
+            .and_then(|o| o.temp)
+            .and_then(|(temp, _)| temp)
+            .ok_or_else(|| decode_error(&format!("アメダス {} の気温がありません", station)))?;
+        Ok((temp_c, observed_at))
+    }
+}
+
+impl WeatherProvider for Jma {
+    fn fetch(&self) -> WeatherFuture<'_> {
+        Box::pin(async move {
+            let reports = self.fetch_forecast().await?;
+            let forecast = parse_forecast(&reports, self.area.as_deref())?;
+            let station = forecast
+                .station
+                .clone()
+                .ok_or_else(|| decode_error("気温の予報地点がありません"))?;
+            let (temp_c, observed_at) = self.fetch_temperature(&station).await?;
+            Ok(forecast.into_weather(temp_c, observed_at))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::test_server;
+
+    const FORECAST: &str = include_str!("fixtures/jma_forecast_130000.json");
+    const AMEDAS_MAP: &str = include_str!("fixtures/jma_amedas_map.json");
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    #[tokio::test]
+    async fn parses_forecast_and_amedas_temperature() {
+        let server = test_server::serve_routes(vec![
+            ("/forecast/data/forecast/130000.json", 200, FORECAST),
+            (
+                "/amedas/data/latest_time.txt",
+                200,
+                "2026-01-12T11:10:00+09:00",
+            ),
+            ("/amedas/data/map/20260112111000.json", 200, AMEDAS_MAP),
+        ]);
+        let provider = Jma::new("130000", Some("130010")).with_base_url(&server.base_url);
+        let weather = provider.fetch().await.unwrap();
+
+        assert_eq!(weather.current.temp_c, 7.8);
+        assert_eq!(weather.current.icon, WeatherIconType::Sun);
+
+        // 降水確率の6時間ごとの予報
+        let pops: Vec<(u32, u8, WeatherIconType)> = weather
+            .hours
+            .iter()
+            .map(|h| (h.time.hour(), h.chance_of_rain, h.icon))
+            .collect();
+        assert_eq!(
+            pops,
+            vec![
+                (12, 10, WeatherIconType::Sun),
+                (18, 20, WeatherIconType::Moon),
+                (0, 60, WeatherIconType::Rain),
+                (6, 80, WeatherIconType::Rain),
+                (12, 40, WeatherIconType::Rain),
+                (18, 10, WeatherIconType::Rain),
+            ]
+        );
+        assert!(weather.hours.iter().all(|h| h.temp_c.is_none()));
+
+        // 今日は最低気温の発表が終わっている。3日目以降は週間予報から
+        let today = weather.day(date(12)).unwrap();
+        assert_eq!((today.maxtemp_c, today.mintemp_c), (Some(10.0), None));
+        let tomorrow = weather.day(date(13)).unwrap();
+        assert_eq!(
+            (tomorrow.maxtemp_c, tomorrow.mintemp_c),
+            (Some(8.0), Some(3.0))
+        );
+        let day3 = weather.day(date(14)).unwrap();
+        assert_eq!((day3.maxtemp_c, day3.mintemp_c), (Some(9.0), Some(1.0)));
+        assert_eq!(weather.days.len(), 7);
+        assert_eq!(today.sunrise, None);
+    }
+
+    #[test]
+    fn other_areas_use_their_own_station() {
+        let reports: Vec<ForecastReport> = serde_json::from_str(FORECAST).unwrap();
+        let forecast = parse_forecast(&reports, Some("130030")).unwrap();
+        assert_eq!(forecast.station.as_deref(), Some("44263"));
+        assert_eq!(forecast.codes[&date(13)], 302);
+
+        let weather = forecast.into_weather(14.8, "2026-01-12T20:00:00+09:00".parse().unwrap());
+        assert_eq!(weather.current.icon, WeatherIconType::Cloud);
+        // 週間予報は八丈島の気温
+        let day3 = weather.day(date(14)).unwrap();
+        assert_eq!((day3.maxtemp_c, day3.mintemp_c), (Some(15.0), Some(11.0)));
+
+        assert!(matches!(
+            parse_forecast(&reports, Some("999999")),
+            Err(WeatherError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn jma_codes_map_to_icons() {
+        assert_eq!(get_weather_icon(100, true), WeatherIconType::Sun);
+        assert_eq!(get_weather_icon(101, false), WeatherIconType::Moon);
+        assert_eq!(get_weather_icon(209, true), WeatherIconType::Cloud);
+        assert_eq!(get_weather_icon(313, true), WeatherIconType::Rain);
+        assert_eq!(get_weather_icon(306, true), WeatherIconType::HeavyRain);
+        assert_eq!(get_weather_icon(405, true), WeatherIconType::Snow);
+        assert_eq!(get_weather_icon(350, true), WeatherIconType::Thunder);
+    }
+}
@@ -0,0 +1,224 @@
+// Open-Meteo (open-meteo.com) からの取得（API キー不要）
+use serde::Deserialize;
+
+use super::{
+    get_body, local_time, status_error, Current, DaySummary, Hour, Weather, WeatherError,
+    WeatherFuture, WeatherIconType, WeatherProvider,
+};
+
+const BASE_URL: &str = "https://api.open-meteo.com";
+
+// 時刻はすべて UNIX 秒 (timeformat=unixtime)、現地時刻への変換は utc_offset_seconds で行う
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    utc_offset_seconds: i32,
+    current: CurrentValues,
+    hourly: HourlyValues,
+    daily: DailyValues,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentValues {
+    temperature_2m: f64,
+    weather_code: u8,
+    is_day: u8,
+}
+
+// 項目ごとの配列（欠測は null）
+#[derive(Debug, Deserialize)]
+struct HourlyValues {
+    time: Vec<i64>,
+    temperature_2m: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<u8>>,
+    weather_code: Vec<Option<u8>>,
+    is_day: Vec<Option<u8>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyValues {
+    // 現地時刻の 0 時
+    time: Vec<i64>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    sunrise: Vec<Option<i64>>,
+    sunset: Vec<Option<i64>>,
+}
+
+// WMO の天気コードからアイコンを取得
+pub fn get_weather_icon(code: u8, is_day: bool) -> WeatherIconType {
+    match code {
+        // 快晴、晴れ
+        0 | 1 => WeatherIconType::clear(is_day),
+        // 一部曇り、曇り、霧
+        2 | 3 | 45 | 48 => WeatherIconType::Cloud,
+        // 霧雨、雨、着氷性の雨（弱〜並）、にわか雨
+        51 | 53 | 55 | 56 | 57 | 61 | 63 | 66 | 80 | 81 => WeatherIconType::Rain,
+        // 強い雨、激しいにわか雨
+        65 | 67 | 82 => WeatherIconType::HeavyRain,
+        // 雪、霧雪、にわか雪
+        71 | 73 | 75 | 77 | 85 | 86 => WeatherIconType::Snow,
+        // 雷雨（ひょうを伴うものを含む）
+        95 | 96 | 99 => WeatherIconType::Thunder,
+        _ => WeatherIconType::Cloud,
+    }
+}
+
+// 共通の形式に変換（天気コードが欠測の時間は除く）
+fn parse_weather(response: ForecastResponse) -> Weather {
+    let offset = response.utc_offset_seconds;
+
+    let hourly = &response.hourly;
+    let hours = hourly
+        .time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &epoch)| {
+            let code = (*hourly.weather_code.get(i)?)?;
+            let is_day = hourly.is_day.get(i).copied().flatten().unwrap_or(1) != 0;
+            Some(Hour {
+                time: local_time(epoch, offset)?,
+                time_epoch: epoch,
+                temp_c: hourly.temperature_2m.get(i).copied().flatten(),
+                chance_of_rain: hourly
+                    .precipitation_probability
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .unwrap_or(0),
+                icon: get_weather_icon(code, is_day),
+            })
+        })
+        .collect();
+
+    let daily = &response.daily;
+    let local_clock = |epoch: Option<i64>| Some(local_time(epoch?, offset)?.time());
+    let days = daily
+        .time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &epoch)| {
+            Some(DaySummary {
+                date: local_time(epoch, offset)?.date(),
+                maxtemp_c: daily.temperature_2m_max.get(i).copied().flatten(),
+                mintemp_c: daily.temperature_2m_min.get(i).copied().flatten(),
+                sunrise: local_clock(daily.sunrise.get(i).copied().flatten()),
+                sunset: local_clock(daily.sunset.get(i).copied().flatten()),
+            })
+        })
+        .collect();
+
+    Weather {
+        current: Current {
+            temp_c: response.current.temperature_2m,
+            icon: get_weather_icon(response.current.weather_code, response.current.is_day != 0),
+        },
+        hours,
+        days,
+    }
+}
+
+// Open-Meteo の設定（地点は緯度・経度で指定）
+pub struct OpenMeteo {
+    base_url: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl OpenMeteo {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            latitude,
+            longitude,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    async fn fetch_forecast(&self) -> Result<ForecastResponse, WeatherError> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}\
+             &current=temperature_2m,weather_code,is_day\
+             &hourly=temperature_2m,precipitation_probability,weather_code,is_day\
+             &daily=temperature_2m_max,temperature_2m_min,sunrise,sunset\
+             &timezone=auto&timeformat=unixtime&forecast_days=2",
+            self.base_url, self.latitude, self.longitude
+        );
+        let body = get_body(&url, status_error).await?;
+        serde_json::from_str(&body).map_err(WeatherError::Decode)
+    }
+}
+
+impl WeatherProvider for OpenMeteo {
+    fn fetch(&self) -> WeatherFuture<'_> {
+        Box::pin(async move { Ok(parse_weather(self.fetch_forecast().await?)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::test_server;
+    use chrono::{NaiveDate, NaiveTime, Timelike};
+
+    const FIXTURE: &str = include_str!("fixtures/open_meteo.json");
+
+    #[tokio::test]
+    async fn parses_recorded_response() {
+        let server = test_server::serve(200, FIXTURE);
+        let provider = OpenMeteo::new(35.68, 139.76).with_base_url(&server.base_url);
+        let weather = provider.fetch().await.unwrap();
+        assert!(server
+            .request_path()
+            .starts_with("/v1/forecast?latitude=35.68&longitude=139.76&"));
+
+        assert_eq!(weather.current.temp_c, 5.3);
+        assert_eq!(weather.current.icon, WeatherIconType::Cloud);
+
+        // 欠測の1時間分は除かれる
+        assert_eq!(weather.hours.len(), 47);
+        assert_eq!(weather.hours[0].time.hour(), 0);
+        assert_eq!(weather.hours[0].icon, WeatherIconType::Moon);
+        let rain = weather.hours.iter().find(|h| h.time.hour() == 15).unwrap();
+        assert_eq!(rain.icon, WeatherIconType::Rain);
+        assert_eq!(rain.chance_of_rain, 65);
+
+        let day = weather
+            .day(NaiveDate::from_ymd_opt(2026, 1, 12).unwrap())
+            .unwrap();
+        assert_eq!((day.maxtemp_c, day.mintemp_c), (Some(9.4), Some(0.8)));
+        assert_eq!(day.sunrise, NaiveTime::from_hms_opt(6, 51, 0));
+        assert_eq!(day.sunset, NaiveTime::from_hms_opt(16, 49, 0));
+        assert_eq!(weather.days.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn error_status_is_reported() {
+        let body = r#"{"error":true,"reason":"Latitude must be in range of -90 to 90°."}"#;
+        let server = test_server::serve(400, body);
+        let provider = OpenMeteo::new(135.0, 139.76).with_base_url(&server.base_url);
+        match provider.fetch().await {
+            Err(WeatherError::HttpStatus(status)) => assert_eq!(status.as_u16(), 400),
+            other => panic!("{:?}", other),
+        }
+
+        let server = test_server::serve(429, r#"{"error":true,"reason":"Too many requests"}"#);
+        let provider = OpenMeteo::new(35.68, 139.76).with_base_url(&server.base_url);
+        assert!(matches!(provider.fetch().await, Err(WeatherError::Quota)));
+    }
+
+    #[test]
+    fn wmo_codes_map_to_icons() {
+        assert_eq!(get_weather_icon(0, true), WeatherIconType::Sun);
+        assert_eq!(get_weather_icon(1, false), WeatherIconType::Moon);
+        assert_eq!(get_weather_icon(45, true), WeatherIconType::Cloud);
+        assert_eq!(get_weather_icon(55, true), WeatherIconType::Rain);
+        assert_eq!(get_weather_icon(82, true), WeatherIconType::HeavyRain);
+        assert_eq!(get_weather_icon(86, true), WeatherIconType::Snow);
+        assert_eq!(get_weather_icon(99, true), WeatherIconType::Thunder);
+    }
+}
@@ -0,0 +1,72 @@
+// テスト用の HTTP サーバー（パスに応じて固定のレスポンスを返す）
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+pub struct TestServer {
+    pub base_url: String,
+    requests: mpsc::Receiver<String>,
+}
+
+impl TestServer {
+    // 受け取った順に、リクエストのパス（クエリを含む）
+    pub fn request_path(&self) -> String {
+        self.requests
+            .recv()
+            .expect("リクエストを受け取っていません")
+    }
+}
+
+// すべてのリクエストに同じレスポンスを返す
+pub fn serve(status: u16, body: &'static str) -> TestServer {
+    serve_routes(vec![("/", status, body)])
+}
+
+// パスが前方一致した最初のレスポンスを返す（一致しなければ 404）
+pub fn serve_routes(routes: Vec<(&'static str, u16, &'static str)>) -> TestServer {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                return;
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            // "GET /path HTTP/1.1" の後、空行までのヘッダーは読み捨てる
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+
+            let path = request_line.split(' ').nth(1).unwrap_or("").to_string();
+            let (status, body) = routes
+                .iter()
+                .find(|(prefix, _, _)| path.starts_with(prefix))
+                .map(|&(_, status, body)| (status, body))
+                .unwrap_or((404, ""));
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            if tx.send(path).is_err() {
+                return;
+            }
+        }
+    });
+
+    TestServer {
+        base_url,
+        requests: rx,
+    }
+}
@@ -0,0 +1,253 @@
+// WeatherAPI (weatherapi.com) からの取得
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use super::{get_body, Weather, WeatherError, WeatherFuture, WeatherIconType, WeatherProvider};
+
+const BASE_URL: &str = "http://api.weatherapi.com";
+
+// 天気APIのレスポンス全体
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WeatherReaponse {
+    pub location: Location,
+    pub current: Current,
+    pub forecast: Forecast,
+}
+
+// 場所情報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Location {
+    pub name: String,
+    pub region: String,
+    pub country: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub tz_id: String,
+    pub localtime_epoch: i64,
+    pub localtime: String,
+}
+
+// 現在の天気情報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Current {
+    pub temp_c: f64,
+    pub is_day: u8,
+    pub condition: Condition,
+}
+
+// 天気状態
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Condition {
+    pub code: u16,
+}
+
+// 予報情報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Forecast {
+    pub forecastday: Vec<ForecastDay>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForecastDay {
+    pub date: String,
+    pub date_epoch: i64,
+    pub day: Day,
+    pub astro: Astro,
+    pub hour: Vec<Hour>,
+}
+
+// 1日分の予報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Day {
+    pub maxtemp_c: f64,
+    pub mintemp_c: f64,
+    pub totalprecip_mm: f64,
+    pub daily_chance_of_rain: u8,
+    pub condition: Condition,
+}
+
+// 日の出・日の入りなど (例: "06:12 AM")
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Astro {
+    pub sunrise: String,
+    pub sunset: String,
+    pub moonrise: String,
+    pub moonset: String,
+}
+
+// 時間ごとの予報
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Hour {
+    pub time: String,
+    pub time_epoch: i64,
+    pub temp_c: f64,
+    pub is_day: u8,
+    pub chance_of_rain: u8,
+    pub condition: Condition,
+}
+
+// "06:12 AM" 形式の時刻を解釈（"No sunset" 等は None）
+fn parse_astro_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s, "%I:%M %p").ok()
+}
+
+// 共通の形式に変換（日付・時刻を解釈できない項目は除く）
+fn parse_weather(response: WeatherReaponse) -> Weather {
+    let mut days = Vec::new();
+    let mut hours = Vec::new();
+    for forecast_day in response.forecast.forecastday {
+        hours.extend(forecast_day.hour.into_iter().filter_map(|h| {
+            Some(super::Hour {
+                time: NaiveDateTime::parse_from_str(&h.time, "%Y-%m-%d %H:%M").ok()?,
+                time_epoch: h.time_epoch,
+                temp_c: Some(h.temp_c),
+                chance_of_rain: h.chance_of_rain,
+                icon: get_weather_icon(h.condition.code, h.is_day != 0),
+            })
+        }));
+        if let Ok(date) = NaiveDate::parse_from_str(&forecast_day.date, "%Y-%m-%d") {
+            days.push(super::DaySummary {
+                date,
+                maxtemp_c: Some(forecast_day.day.maxtemp_c),
+                mintemp_c: Some(forecast_day.day.mintemp_c),
+                sunrise: parse_astro_time(&forecast_day.astro.sunrise),
+                sunset: parse_astro_time(&forecast_day.astro.sunset),
+            });
+        }
+    }
+
+    Weather {
+        current: super::Current {
+            temp_c: response.current.temp_c,
+            icon: get_weather_icon(
+                response.current.condition.code,
+                response.current.is_day != 0,
+            ),
+        },
+        hours,
+        days,
+    }
+}
+
+// エラーレスポンスから失敗理由を判別
+fn from_status(status: reqwest::StatusCode, body: &str) -> WeatherError {
+    // WeatherAPI のエラー本文: {"error":{"code":2007,"message":"..."}}
+    #[derive(Deserialize)]
+    struct ErrorBody {
+        error: ErrorDetail,
+    }
+    #[derive(Deserialize)]
+    struct ErrorDetail {
+        code: u16,
+    }
+
+    let code = serde_json::from_str::<ErrorBody>(body)
+        .ok()
+        .map(|b| b.error.code);
+    match code {
+        // 2007: 月間呼び出し上限超過, 2009: プランで利用できない
+        Some(2007) | Some(2009) => WeatherError::Quota,
+        _ => super::status_error(status, body),
+    }
+}
+
+// 天気コードからアイコンを取得
+pub fn get_weather_icon(code: u16, is_day: bool) -> WeatherIconType {
+    match code {
+        // 晴れ
+        1000 => WeatherIconType::clear(is_day),
+        // 曇り、霧（晴れ時々曇りも含む）
+        1003 | 1006 | 1009 | 1030 | 1135 | 1147 => WeatherIconType::Cloud,
+        // 小雨〜並の雨
+        1063 | 1072 | 1150 | 1153 | 1168 | 1180 | 1183 | 1186 | 1189 | 1198 | 1240 => {
+            WeatherIconType::Rain
+        }
+        // 大雨、激しい雨
+        1171 | 1192 | 1195 | 1201 | 1243 | 1246 => WeatherIconType::HeavyRain,
+        // 雪、みぞれ、あられ
+        1066 | 1069 | 1114 | 1117 | 1204 | 1207 | 1210 | 1213 | 1216 | 1219 | 1222 | 1225
+        | 1237 | 1249 | 1252 | 1255 | 1258 | 1261 | 1264 => WeatherIconType::Snow,
+        // 雷
+        1087 | 1273 | 1276 | 1279 | 1282 => WeatherIconType::Thunder,
+        _ => WeatherIconType::Cloud,
+    }
+}
+
+// WeatherAPI の設定（API キーが必要）
+pub struct WeatherApi {
+    base_url: String,
+    api_key: String,
+    // 地名や "緯度,経度" など WeatherAPI の q パラメータ
+    location: String,
+}
+
+impl WeatherApi {
+    pub fn new(api_key: &str, location: &str) -> Self {
+        Self {
+            base_url: BASE_URL.to_string(),
+            api_key: api_key.to_string(),
+            location: location.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    pub async fn fetch_weather(&self) -> Result<WeatherReaponse, WeatherError> {
+        let url = format!(
+            "{}/v1/forecast.json?key={}&q={}&days=2",
+            self.base_url, self.api_key, self.location
+        );
+        let body = get_body(&url, from_status).await?;
+        serde_json::from_str(&body).map_err(WeatherError::Decode)
+    }
+}
+
+impl WeatherProvider for WeatherApi {
+    fn fetch(&self) -> WeatherFuture<'_> {
+        Box::pin(async move { Ok(parse_weather(self.fetch_weather().await?)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::test_server;
+
+    const FIXTURE: &str = include_str!("fixtures/weatherapi.json");
+
+    #[tokio::test]
+    async fn parses_recorded_response() {
+        let server = test_server::serve(200, FIXTURE);
+        let provider = WeatherApi::new("KEY", "Tokyo").with_base_url(&server.base_url);
+        let weather = provider.fetch().await.unwrap();
+        assert!(server
+            .request_path()
+            .starts_with("/v1/forecast.json?key=KEY&q=Tokyo"));
+
+        assert_eq!(weather.current.temp_c, 5.2);
+        assert_eq!(weather.current.icon, WeatherIconType::Sun);
+        assert_eq!(weather.hours.len(), 6);
+        assert_eq!(weather.hours[4].icon, WeatherIconType::Rain);
+        assert_eq!(weather.hours[4].chance_of_rain, 78);
+
+        let day = weather
+            .day(NaiveDate::from_ymd_opt(2026, 1, 12).unwrap())
+            .unwrap();
+        assert_eq!((day.maxtemp_c, day.mintemp_c), (Some(9.4), Some(0.8)));
+        assert_eq!(day.sunrise, NaiveTime::from_hms_opt(6, 51, 0));
+        assert_eq!(day.sunset, NaiveTime::from_hms_opt(16, 49, 0));
+    }
+
+    #[tokio::test]
+    async fn quota_error_code_is_reported() {
+        let body =
+            r#"{"error":{"code":2007,"message":"API key has exceeded calls per month quota."}}"#;
+        let server = test_server::serve(403, body);
+        let provider = WeatherApi::new("KEY", "Tokyo").with_base_url(&server.base_url);
+        assert!(matches!(provider.fetch().await, Err(WeatherError::Quota)));
+    }
+}
@@ -0,0 +1,544 @@
+// 画面を構成するパネル（ウィジェット）と、それを並べるコンテナ
+// 並べ方は設定ファイルの [screen] で指定する
+use chrono::{DateTime, Days, Local, TimeDelta, Timelike};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::clock::{date_unit_size, draw_date, draw_time, time_unit_size};
+use crate::current::{current_unit_size, draw_current};
+use crate::day_summary::{day_summary_unit_size, draw_day_summary};
+use crate::forecast::{self, draw_forecast, forecast_unit_size};
+use crate::framebuffer::{to_u32, FrameBuffer};
+use crate::layout::Rect;
+use crate::render::{Sections, Theme, WeatherView};
+use crate::weather::Weather;
+
+// 古い天気データを表示するときの明るさ
+const STALE_BRIGHTNESS: f32 = 0.45;
+
+// コロンの点滅周期の半分（表示・非表示それぞれの長さ）
+const BLINK_HALF_PERIOD_MS: u32 = 500;
+
+// 設定で指定できる size（大きさの比）の上限
+const MAX_SIZE: i32 = 10000;
+
+// grid の columns の上限
+const MAX_COLUMNS: usize = 100;
+
+pub fn blink_visible(now: &DateTime<Local>) -> bool {
+    now.timestamp_subsec_millis() < BLINK_HALF_PERIOD_MS
+}
+
+// 次の分の始まり
+fn next_minute(now: &DateTime<Local>) -> DateTime<Local> {
+    let elapsed =
+        TimeDelta::seconds(now.second() as i64) + TimeDelta::nanoseconds(now.nanosecond() as i64);
+    *now - elapsed + TimeDelta::minutes(1)
+}
+
+// 描画時に各パネルへ渡す情報
+pub struct Context<'a> {
+    pub now: &'a DateTime<Local>,
+    pub view: &'a WeatherView<'a>,
+    pub theme: &'a Theme,
+}
+
+pub trait Widget {
+    // pixel_size=1 のときの (幅, 高さ)。コンテナで大きさを指定しなかったときの配分に使う
+    fn measure(&self, ctx: &Context) -> (i32, i32);
+
+    // region 内に描画
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context);
+
+    // 時間の経過で次に表示が変わる時刻（天気データや設定の更新によるものは除く）
+    fn next_redraw(&self, _ctx: &Context) -> Option<DateTime<Local>> {
+        None
+    }
+}
+
+pub struct DateWidget;
+
+impl Widget for DateWidget {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        date_unit_size(ctx.now.date_naive(), &ctx.theme.font, ctx.theme.locale)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let theme = ctx.theme;
+        let date = ctx.now.date_naive();
+        draw_date(
+            fb,
+            region,
+            date,
+            &theme.font,
+            theme.locale,
+            theme.colors.date,
+        );
+    }
+
+    // 翌日の 0 時（夏時間の切り替えで存在しなければ次の分）
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        let tomorrow = ctx.now.date_naive().checked_add_days(Days::new(1))?;
+        tomorrow
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(Local)
+            .earliest()
+            .or_else(|| Some(next_minute(ctx.now)))
+    }
+}
+
+pub struct TimeWidget {
+    // false ならコロンを点滅させない
+    pub blink: bool,
+}
+
+impl Widget for TimeWidget {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        let now = ctx.now;
+        time_unit_size(now.hour() as u8, now.minute() as u8, ctx.theme.time_format)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let now = ctx.now;
+        let theme = ctx.theme;
+        let blink = !self.blink || blink_visible(now);
+        draw_time(
+            fb,
+            region,
+            now.hour() as u8,
+            now.minute() as u8,
+            blink,
+            theme.time_format,
+            theme.colors.time,
+        );
+    }
+
+    // 点滅させるなら表示・非表示の切り替わり、そうでなければ次の分
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        let now = ctx.now;
+        if !self.blink {
+            return Some(next_minute(now));
+        }
+        let half_period = BLINK_HALF_PERIOD_MS * 1_000_000;
+        let elapsed = now.timestamp_subsec_nanos() % half_period;
+        Some(*now + TimeDelta::nanoseconds((half_period - elapsed) as i64))
+    }
+}
+
+// 天気データがあれば描画し、古いデータは暗く表示
+fn draw_weather(
+    fb: &mut FrameBuffer,
+    region: &Rect,
+    ctx: &Context,
+    draw: impl FnOnce(&mut FrameBuffer, &Weather),
+) {
+    let Some(weather) = ctx.view.weather else {
+        return;
+    };
+    draw(fb, weather);
+    if ctx.view.stale_age.is_some() {
+        let background = to_u32(ctx.theme.colors.background);
+        fb.dim(region, background, STALE_BRIGHTNESS);
+    }
+}
+
+// 最高・最低気温と日の出・日の入り
+pub struct SummaryWidget;
+
+impl Widget for SummaryWidget {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        let theme = ctx.theme;
+        match ctx.view.weather {
+            Some(weather) => {
+                day_summary_unit_size(weather, ctx.now, &theme.colors, &theme.font, theme.locale)
+            }
+            None => (0, theme.font.height),
+        }
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let theme = ctx.theme;
+        draw_weather(fb, region, ctx, |fb, weather| {
+            draw_day_summary(
+                fb,
+                region,
+                weather,
+                ctx.now,
+                &theme.colors,
+                &theme.font,
+                theme.locale,
+            );
+        });
+    }
+
+    // 日の入り後に明日の概要へ切り替わるので分ごとに確認する
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        ctx.view.weather.map(|_| next_minute(ctx.now))
+    }
+}
+
+// 現在の天気
+pub struct CurrentWidget;
+
+impl Widget for CurrentWidget {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        let theme = ctx.theme;
+        match ctx.view.weather {
+            Some(weather) => current_unit_size(&weather.current, &theme.font, theme.locale),
+            None => (0, 0),
+        }
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let theme = ctx.theme;
+        draw_weather(fb, region, ctx, |fb, weather| {
+            draw_current(
+                fb,
+                region,
+                &weather.current,
+                &theme.font,
+                theme.locale,
+                theme.colors.current,
+            );
+        });
+    }
+}
+
+// 時間ごとの予報
+pub struct ForecastWidget;
+
+impl Widget for ForecastWidget {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        forecast_unit_size(&ctx.theme.font)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        draw_weather(fb, region, ctx, |fb, weather| {
+            let hours = weather.upcoming(ctx.now.timestamp(), forecast::MAX_ITEMS);
+            draw_forecast(fb, region, hours, ctx.theme);
+        });
+    }
+
+    // 先頭の予報の時刻を過ぎると次の時間にずれる
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        let weather = ctx.view.weather?;
+        let first = weather.upcoming(ctx.now.timestamp(), 1).first()?;
+        DateTime::from_timestamp(first.time_epoch + 1, 0).map(|at| at.with_timezone(&Local))
+    }
+}
+
+// 何も描かない余白
+pub struct Spacer;
+
+impl Widget for Spacer {
+    fn measure(&self, _ctx: &Context) -> (i32, i32) {
+        (0, 0)
+    }
+
+    fn draw(&self, _fb: &mut FrameBuffer, _region: &Rect, _ctx: &Context) {}
+}
+
+// コンテナの子。size は並べる方向の大きさの比（None なら measure の大きさ）
+pub struct Child {
+    pub widget: Box<dyn Widget>,
+    pub size: Option<i32>,
+}
+
+// 子のうち最も早く再描画が必要な時刻
+fn earliest_redraw<'a>(
+    widgets: impl Iterator<Item = &'a dyn Widget>,
+    ctx: &Context,
+) -> Option<DateTime<Local>> {
+    widgets.filter_map(|widget| widget.next_redraw(ctx)).min()
+}
+
+// 子を縦に並べる
+pub struct Column(pub Vec<Child>);
+
+impl Widget for Column {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        self.0.iter().fold((0, 0), |(width, height), child| {
+            let (w, h) = child.widget.measure(ctx);
+            (width.max(w), height + h)
+        })
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let weights: Vec<i32> = (self.0.iter())
+            .map(|child| child.size.unwrap_or_else(|| child.widget.measure(ctx).1))
+            .collect();
+        for (child, rect) in self.0.iter().zip(region.split_rows(&weights)) {
+            child.widget.draw(fb, &rect, ctx);
+        }
+    }
+
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        earliest_redraw(self.0.iter().map(|child| child.widget.as_ref()), ctx)
+    }
+}
+
+// 子を横に並べる
+pub struct Row(pub Vec<Child>);
+
+impl Widget for Row {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        self.0.iter().fold((0, 0), |(width, height), child| {
+            let (w, h) = child.widget.measure(ctx);
+            (width + w, height.max(h))
+        })
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let weights: Vec<i32> = (self.0.iter())
+            .map(|child| child.size.unwrap_or_else(|| child.widget.measure(ctx).0))
+            .collect();
+        for (child, rect) in self.0.iter().zip(region.split_columns(&weights)) {
+            child.widget.draw(fb, &rect, ctx);
+        }
+    }
+
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        earliest_redraw(self.0.iter().map(|child| child.widget.as_ref()), ctx)
+    }
+}
+
+// 子を columns 列の同じ大きさのセルに左上から順に並べる
+pub struct Grid {
+    pub columns: usize,
+    pub children: Vec<Box<dyn Widget>>,
+}
+
+impl Grid {
+    fn rows(&self) -> usize {
+        self.children.len().div_ceil(self.columns)
+    }
+}
+
+impl Widget for Grid {
+    fn measure(&self, ctx: &Context) -> (i32, i32) {
+        let (width, height) = self.children.iter().fold((0, 0), |(width, height), child| {
+            let (w, h) = child.measure(ctx);
+            (width.max(w), height.max(h))
+        });
+        (width * self.columns as i32, height * self.rows() as i32)
+    }
+
+    fn draw(&self, fb: &mut FrameBuffer, region: &Rect, ctx: &Context) {
+        let cells = region
+            .split_rows(&vec![1; self.rows()])
+            .into_iter()
+            .flat_map(|row| row.split_columns(&vec![1; self.columns]));
+        for (child, cell) in self.children.iter().zip(cells) {
+            child.draw(fb, &cell, ctx);
+        }
+    }
+
+    fn next_redraw(&self, ctx: &Context) -> Option<DateTime<Local>> {
+        earliest_redraw(self.children.iter().map(|child| child.as_ref()), ctx)
+    }
+}
+
+// パネルの種類（設定ファイルの type）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelKind {
+    Date,
+    Time,
+    // 最高・最低気温と日の出・日の入り
+    Summary,
+    Current,
+    Forecast,
+    Spacer,
+    Row,
+    Column,
+    Grid,
+}
+
+// 設定ファイルでのパネルの指定（[screen] とその children）
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Panel {
+    #[serde(rename = "type")]
+    pub kind: PanelKind,
+    // 親の row / column の中での大きさの比（省略時は中身の大きさの比）
+    pub size: Option<i32>,
+    // row / column / grid に並べるパネル
+    #[serde(default)]
+    pub children: Vec<Panel>,
+    // grid の列数
+    pub columns: Option<usize>,
+}
+
+impl Panel {
+    fn new(kind: PanelKind, size: i32) -> Self {
+        Self {
+            kind,
+            size: Some(size),
+            children: Vec::new(),
+            columns: None,
+        }
+    }
+
+    // 組み合わせの誤りを設定の読み込み時に見つける
+    pub fn validate(&self) -> Result<(), String> {
+        let is_container = matches!(
+            self.kind,
+            PanelKind::Row | PanelKind::Column | PanelKind::Grid
+        );
+        if self
+            .size
+            .is_some_and(|size| !(0..=MAX_SIZE).contains(&size))
+        {
+            return Err(format!("size は 0〜{} で指定してください", MAX_SIZE));
+        }
+        if is_container && self.children.is_empty() {
+            return Err(format!("{:?} には children が必要です", self.kind));
+        }
+        if !is_container && !self.children.is_empty() {
+            return Err(format!("{:?} には children を指定できません", self.kind));
+        }
+        match (self.kind, self.columns) {
+            (PanelKind::Grid, Some(columns)) if (1..=MAX_COLUMNS).contains(&columns) => {}
+            (PanelKind::Grid, _) => {
+                return Err(format!(
+                    "grid の columns は 1〜{} で指定してください",
+                    MAX_COLUMNS
+                ))
+            }
+            (_, Some(_)) => return Err("columns は grid にだけ指定できます".to_string()),
+            (_, None) => {}
+        }
+        // grid のセルはすべて同じ大きさなので、子の size は受け付けない
+        if self.kind == PanelKind::Grid && self.children.iter().any(|c| c.size.is_some()) {
+            return Err("grid の children には size を指定できません".to_string());
+        }
+        self.children.iter().try_for_each(Panel::validate)
+    }
+
+    // ウィジェットを組み立てる。[layout] で表示しない項目は余白にする
+    pub fn build(&self, sections: &Sections) -> Box<dyn Widget> {
+        let children = || {
+            (self.children.iter())
+                .map(|panel| Child {
+                    widget: panel.build(sections),
+                    size: panel.size,
+                })
+                .collect()
+        };
+        match self.kind {
+            PanelKind::Date if sections.date => Box::new(DateWidget),
+            PanelKind::Time => Box::new(TimeWidget {
+                blink: sections.blink_colon,
+            }),
+            PanelKind::Summary if sections.summary => Box::new(SummaryWidget),
+            PanelKind::Current if sections.current => Box::new(CurrentWidget),
+            PanelKind::Forecast if sections.forecast => Box::new(ForecastWidget),
+            PanelKind::Row => Box::new(Row(children())),
+            PanelKind::Column => Box::new(Column(children())),
+            PanelKind::Grid => Box::new(Grid {
+                columns: self.columns.unwrap_or(1).max(1),
+                children: children().into_iter().map(|child| child.widget).collect(),
+            }),
+            _ => Box::new(Spacer),
+        }
+    }
+}
+
+// 組み立て済みの画面。設定を読み込んだときに一度だけ作り、描画のたびに使い回す
+#[derive(Clone)]
+pub struct Screen(Arc<dyn Widget>);
+
+impl Screen {
+    pub fn new(panel: &Panel, sections: &Sections) -> Self {
+        Self(Arc::from(panel.build(sections)))
+    }
+
+    pub fn widget(&self) -> &dyn Widget {
+        self.0.as_ref()
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new(&Panel::default(), &Sections::default())
+    }
+}
+
+impl std::fmt::Debug for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Screen").finish_non_exhaustive()
+    }
+}
+
+// 既定の画面: 日付・今日の概要・時刻・現在の天気・予報を縦に並べる
+// 大きさは基準画面 (480px) での高さ
+impl Default for Panel {
+    fn default() -> Self {
+        let children = [
+            (PanelKind::Spacer, 10),
+            (PanelKind::Date, 36),
+            (PanelKind::Spacer, 10),
+            (PanelKind::Summary, 24),
+            (PanelKind::Spacer, 12),
+            (PanelKind::Time, 168),
+            (PanelKind::Spacer, 8),
+            (PanelKind::Current, 64),
+            (PanelKind::Spacer, 12),
+            (PanelKind::Forecast, 136),
+        ];
+        Self {
+            kind: PanelKind::Column,
+            size: None,
+            children: children
+                .into_iter()
+                .map(|(kind, size)| Panel::new(kind, size))
+                .collect(),
+            columns: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn next_redraw_is_the_earliest_change() {
+        let now = NaiveDate::from_ymd_opt(2026, 1, 12)
+            .unwrap()
+            .and_hms_milli_opt(9, 30, 15, 200)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap();
+        let view = WeatherView::default();
+        let theme = Theme::default();
+        let ctx = Context {
+            now: &now,
+            view: &view,
+            theme: &theme,
+        };
+
+        // 点滅の切り替わりは 0.5 秒ごと、点滅しなければ次の分
+        let blinking = TimeWidget { blink: true };
+        let steady = TimeWidget { blink: false };
+        assert_eq!(
+            blinking.next_redraw(&ctx),
+            Some(now + TimeDelta::milliseconds(300))
+        );
+        assert_eq!(
+            steady.next_redraw(&ctx),
+            Some(now + TimeDelta::milliseconds(44_800))
+        );
+
+        // コンテナは子のうち最も早い時刻。天気がなければ現在の天気は再描画しない
+        let next_minute = steady.next_redraw(&ctx);
+        let child = |widget: Box<dyn Widget>| Child { widget, size: None };
+        let row = Row(vec![
+            child(Box::new(DateWidget)),
+            child(Box::new(steady)),
+            child(Box::new(CurrentWidget)),
+        ]);
+        assert_eq!(row.next_redraw(&ctx), next_minute);
+    }
+}